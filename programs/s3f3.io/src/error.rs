@@ -41,5 +41,67 @@ pub enum ErrorCode {
     #[msg("Division error")]
     DivisionError,
     #[msg("Insufficient liquid staking token")]
-    InsufficientLiquidStakingToken
+    InsufficientLiquidStakingToken,
+    #[msg("Fee rate exceeds the protocol maximum")]
+    FeeRateTooHigh,
+    #[msg("Insufficient protocol fees")]
+    InsufficientProtocolFees,
+    #[msg("No pending config authority")]
+    NoPendingAuthority,
+    #[msg("Guardian can only disable operations")]
+    GuardianCannotEnable,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    #[msg("Slippage tolerance exceeded")]
+    SlippageExceeded,
+    #[msg("Math underflow")]
+    MathUnderflow,
+    #[msg("Invalid lookup table data")]
+    InvalidLookupTableData,
+    #[msg("Escrow stake amount decreased")]
+    EscrowBalanceDecreased,
+    #[msg("Account does not match the pool's Meteora accounts")]
+    InvalidMeteoraAccount,
+    #[msg("Lookup table has too few addresses")]
+    LookupTableTooShort,
+    #[msg("Remaining account does not match the lookup table")]
+    LookupTableAccountMismatch,
+    #[msg("Too many lookup table addresses")]
+    TooManyLookupTableAddresses,
+    #[msg("Duplicate lookup table address")]
+    DuplicateLookupTableAddress,
+    #[msg("A lookup table is already staged or awaiting close")]
+    LookupTableInUse,
+    #[msg("Compound swap route is not set")]
+    CompoundRouteNotSet,
+    #[msg("Nothing to compound")]
+    NothingToCompound,
+    #[msg("Instant unstake buffer is too small")]
+    InsufficientBuffer,
+    #[msg("A buffer unstake is already in flight")]
+    BufferUnstakeInFlight,
+    #[msg("No buffer unstake in flight")]
+    NoBufferUnstake,
+    #[msg("Instant unstake buffer is at its target")]
+    BufferAtTarget,
+    #[msg("Unstake bucket is not in the expected state")]
+    InvalidUnstakeBucketStatus,
+    #[msg("Unstake bucket epoch has not ended")]
+    UnstakeBucketEpochNotEnded,
+    #[msg("Unstake bucket is empty")]
+    EmptyUnstakeBucket,
+    #[msg("Unstake ticket accounts missing or invalid")]
+    InvalidUnstakeTicket,
+    #[msg("Config is already migrated")]
+    ConfigAlreadyMigrated,
+    #[msg("Rate history account is required for this pool")]
+    RateHistoryRequired,
+    #[msg("Compound swap spent more quote than was compoundable")]
+    CompoundOverspent,
+    #[msg("Compound slippage bound is invalid")]
+    InvalidCompoundSlippage,
+    #[msg("Checkpointed LST is still within the minimum checkpoint duration")]
+    CheckpointLocked,
+    #[msg("Unstake amount is zero or rounds to zero")]
+    ZeroUnstakeAmount,
 }
@@ -0,0 +1,353 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PoolFeesUpdated {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub old_deposit_fee_rate: u64,
+    pub new_deposit_fee_rate: u64,
+    pub old_reward_fee_rate: u64,
+    pub new_reward_fee_rate: u64,
+}
+
+#[event]
+pub struct ProtocolFeesWithdrawn {
+    pub pool: Pubkey,
+    pub token_amount: u64,
+    pub quote_amount: u64,
+    pub remaining_protocol_fees_token: u64,
+    pub remaining_protocol_fees_quote: u64,
+}
+
+#[event]
+pub struct ConfigAuthorityProposed {
+    pub config: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct ConfigAuthorityTransferred {
+    pub config: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct PoolStatusUpdated {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub old_status: u8,
+    pub new_status: u8,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub config: Pubkey,
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+}
+
+#[event]
+pub struct GlobalPauseUpdated {
+    pub config: Pubkey,
+    pub authority: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct ConfigInitialized {
+    pub config: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub config: Pubkey,
+    pub authority: Pubkey,
+    pub old_len: u64,
+    pub new_len: u64,
+}
+
+#[event]
+pub struct FeeRateCapsUpdated {
+    pub config: Pubkey,
+    pub old_max_deposit_fee_rate: u64,
+    pub new_max_deposit_fee_rate: u64,
+    pub old_max_reward_fee_rate: u64,
+    pub new_max_reward_fee_rate: u64,
+}
+
+#[event]
+pub struct PoolInitialized {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub vault: Pubkey,
+    pub token_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub liquid_token_mint: Pubkey,
+    pub deposit_fee_rate: u64,
+    pub reward_fee_rate: u64,
+}
+
+#[event]
+pub struct EscrowInitialized {
+    pub pool: Pubkey,
+    pub vault: Pubkey,
+    pub escrow: Pubkey,
+}
+
+#[event]
+pub struct MeteoraAccountsBackfilled {
+    pub pool: Pubkey,
+    pub vault: Pubkey,
+    pub fee_pool: Pubkey,
+}
+
+#[event]
+pub struct Staked {
+    pub pool: Pubkey,
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub deposit_fee: u64,
+    pub liquid_amount_out: u64,
+    pub total_backing: u64,
+    pub liquid_supply: u64,
+}
+
+#[event]
+pub struct UnstakeRequested {
+    pub pool: Pubkey,
+    pub staker: Pubkey,
+    pub lst_unstake_request: Pubkey,
+    pub unstake: Pubkey,
+    pub ticket_mint: Pubkey,
+    pub lst_amount: u64,
+    pub token_amount: u64,
+    pub exchange_rate: u64,
+    pub liquid_supply: u64,
+}
+
+#[event]
+pub struct UnstakeRequestMigrated {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub lst_unstake_request: Pubkey,
+    pub unstake: Pubkey,
+    pub token_amount: u64,
+}
+
+#[event]
+pub struct UnstakeCancelled {
+    pub pool: Pubkey,
+    pub staker: Pubkey,
+    pub lst_unstake_request: Pubkey,
+    pub unstake: Pubkey,
+    pub token_amount: u64,
+    pub liquid_amount_out: u64,
+    pub liquid_supply: u64,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub pool: Pubkey,
+    pub staker: Pubkey,
+    pub lst_unstake_request: Pubkey,
+    pub unstake: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FeesClaimed {
+    pub pool: Pubkey,
+    pub claimer: Pubkey,
+    pub staked_amount_change: u64,
+    pub protocol_fee: u64,
+    pub protocol_fees_token: u64,
+    pub quote_amount_change: u64,
+    pub quote_protocol_fee: u64,
+    pub protocol_fees_quote: u64,
+    pub total_backing: u64,
+    pub liquid_supply: u64,
+}
+
+#[event]
+pub struct LookupTableCreated {
+    pub pool: Pubkey,
+    pub lookup_table: Pubkey,
+    pub pending: bool,
+}
+
+#[event]
+pub struct LookupTableExtended {
+    pub pool: Pubkey,
+    pub lookup_table: Pubkey,
+    pub new_addresses: Vec<Pubkey>,
+}
+
+#[event]
+pub struct LookupTableFrozen {
+    pub pool: Pubkey,
+    pub lookup_table: Pubkey,
+}
+
+#[event]
+pub struct LookupTableReplaced {
+    pub pool: Pubkey,
+    pub old_lookup_table: Pubkey,
+    pub new_lookup_table: Pubkey,
+    pub retired: bool,
+}
+
+#[event]
+pub struct LookupTableDeactivated {
+    pub pool: Pubkey,
+    pub lookup_table: Pubkey,
+}
+
+#[event]
+pub struct LookupTableClosed {
+    pub pool: Pubkey,
+    pub lookup_table: Pubkey,
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct CompoundRouteUpdated {
+    pub pool: Pubkey,
+    pub swap_program: Pubkey,
+    pub swap_pool: Pubkey,
+    pub max_slippage_bps: u64,
+}
+
+#[event]
+pub struct Compounded {
+    pub pool: Pubkey,
+    pub caller: Pubkey,
+    pub quote_in: u64,
+    pub token_out: u64,
+    pub total_backing: u64,
+    pub liquid_supply: u64,
+}
+
+#[event]
+pub struct QuoteRewardModeUpdated {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub distribute_quote_rewards: bool,
+}
+
+#[event]
+pub struct QuoteRewardsSettled {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub deposit_amount: u64,
+    pub withdraw_amount: u64,
+    pub lst_amount: u64,
+    pub accrued_quote: u64,
+    pub quote_reward_per_lst: u128,
+}
+
+#[event]
+pub struct QuoteRewardsClaimed {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub quote_rewards_reserved: u64,
+}
+
+#[event]
+pub struct InstantUnstakeParamsUpdated {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub instant_unstake_fee_rate: u64,
+    pub buffer_target: u64,
+}
+
+#[event]
+pub struct InstantUnstaked {
+    pub pool: Pubkey,
+    pub staker: Pubkey,
+    pub lst_amount: u64,
+    pub token_amount: u64,
+    pub exit_fee: u64,
+    pub buffer: u64,
+    pub liquid_supply: u64,
+}
+
+#[event]
+pub struct BufferReplenishRequested {
+    pub pool: Pubkey,
+    pub cranker: Pubkey,
+    pub unstake: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct BufferReplenished {
+    pub pool: Pubkey,
+    pub cranker: Pubkey,
+    pub unstake: Pubkey,
+    pub amount: u64,
+    pub buffer: u64,
+}
+
+#[event]
+pub struct UnstakeEnqueued {
+    pub pool: Pubkey,
+    pub staker: Pubkey,
+    pub bucket: Pubkey,
+    pub bucket_id: u64,
+    pub lst_amount: u64,
+    pub token_amount: u64,
+    pub liquid_supply: u64,
+}
+
+#[event]
+pub struct UnstakeBucketRequested {
+    pub pool: Pubkey,
+    pub bucket: Pubkey,
+    pub bucket_id: u64,
+    pub unstake: Pubkey,
+    pub token_amount: u64,
+}
+
+#[event]
+pub struct UnstakeBucketWithdrawn {
+    pub pool: Pubkey,
+    pub bucket: Pubkey,
+    pub bucket_id: u64,
+    pub withdrawn_amount: u64,
+}
+
+#[event]
+pub struct UnstakeTicketClaimed {
+    pub pool: Pubkey,
+    pub staker: Pubkey,
+    pub bucket: Pubkey,
+    pub bucket_id: u64,
+    pub lst_amount: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RateHistoryInitialized {
+    pub pool: Pubkey,
+    pub payer: Pubkey,
+    pub rate_history: Pubkey,
+}
+
+#[event]
+pub struct StakerPositionInitialized {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub staker_position: Pubkey,
+}
+
+#[event]
+pub struct QuoteRewardCheckpointInitialized {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub checkpoint: Pubkey,
+    pub reward_per_lst_paid: u128,
+}
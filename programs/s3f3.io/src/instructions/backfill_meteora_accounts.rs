@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::states::config::Config;
+use crate::states::pool::LiquidStakingPool;
+use crate::events::MeteoraAccountsBackfilled;
+use crate::utils::meteora;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BackfillMeteoraAccounts<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = pool.escrow != Pubkey::default() @ ErrorCode::NotApproved,
+        constraint = pool.fee_pool == Pubkey::default() @ ErrorCode::EscrowAlreadyInitialized,
+    )]
+    pub pool: Box<Account<'info, LiquidStakingPool>>,
+    /// CHECK: deserialized as a Meteora FeeVault
+    #[account(
+        constraint = vault.key() == pool.vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: checked against the FeeVault pool
+    pub fee_pool: UncheckedAccount<'info>,
+}
+
+/// Permissionless: records the Meteora accounts for pools whose escrow was initialized
+/// before they were stored, which every CPI context now checks its accounts against
+pub fn backfill_meteora_accounts(ctx: Context<BackfillMeteoraAccounts>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    meteora::record_fee_vault_accounts(pool, &ctx.accounts.vault, &ctx.accounts.fee_pool)?;
+
+    emit_cpi!(MeteoraAccountsBackfilled {
+        pool: pool.key(),
+        vault: ctx.accounts.vault.key(),
+        fee_pool: pool.fee_pool,
+    });
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod request;
+pub use request::*;
+
+pub mod withdraw;
+pub use withdraw::*;
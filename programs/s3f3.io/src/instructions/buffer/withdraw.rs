@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use stake_for_fee_interface::{
+    instructions::withdraw_invoke_signed,
+    id as stake_for_fee_id,
+    WithdrawAccounts,
+};
+use crate::states::config::Config;
+use crate::states::pool::LiquidStakingPool;
+use crate::AUTH_SEED;
+use crate::error::ErrorCode;
+use crate::events::BufferReplenished;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawBufferReplenish<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        constraint = liquid_staking_pool.buffer_unstake != Pubkey::default() @ ErrorCode::NoBufferUnstake,
+    )]
+    pub liquid_staking_pool: Box<Account<'info, LiquidStakingPool>>,
+    /// CHECK
+    #[account(
+        mut,
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = liquid_staking_pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = token_vault.key() == liquid_staking_pool.token_vault_account @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = unstake.key() == liquid_staking_pool.buffer_unstake @ ErrorCode::NoBufferUnstake,
+    )]
+    pub unstake: UncheckedAccount<'info>,
+    /// CHECK:
+    pub cpi_program: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = vault.key() == liquid_staking_pool.vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = stake_token_vault.key() == liquid_staking_pool.stake_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub stake_token_vault: UncheckedAccount<'info>,
+    /// CHECK: StakeEscrow is handled by CPI
+    #[account(
+        mut,
+        constraint = stake_escrow.key() == liquid_staking_pool.escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub stake_escrow: UncheckedAccount<'info>,
+    /// CHECK:
+    pub token_program: UncheckedAccount<'info>,
+    /// CHECK:
+    pub cpi_event_authority: UncheckedAccount<'info>,
+    /// CHECK:
+    pub system_program: UncheckedAccount<'info>,
+}
+
+/// Permissionless crank: withdraws the matured buffer unstake into the token vault
+pub fn withdraw_buffer_replenish(ctx: Context<WithdrawBufferReplenish>) -> Result<()> {
+    if ctx.accounts.cpi_program.key() != stake_for_fee_id() {
+        return err!(ErrorCode::InvalidCpiProgram);
+    }
+    let liquid_staking_pool = &mut ctx.accounts.liquid_staking_pool;
+    let accounts_cpi: WithdrawAccounts<'_, '_> = WithdrawAccounts {
+        unstake: &ctx.accounts.unstake.to_account_info(),
+        vault: &ctx.accounts.vault.to_account_info(),
+        user_stake_token: &ctx.accounts.token_vault.to_account_info(),
+        stake_token_vault: &ctx.accounts.stake_token_vault.to_account_info(),
+        stake_escrow: &ctx.accounts.stake_escrow.to_account_info(),
+        owner: &ctx.accounts.authority.to_account_info(),
+        token_program: &ctx.accounts.token_program.to_account_info(),
+        event_authority: &ctx.accounts.cpi_event_authority.to_account_info(),
+        program: &ctx.accounts.cpi_program.to_account_info(),
+    };
+    let balance_before = ctx.accounts.token_vault.amount;
+    withdraw_invoke_signed(accounts_cpi, &[&[crate::AUTH_SEED.as_bytes(), &[liquid_staking_pool.auth_bump]]])?;
+
+    ctx.accounts.token_vault.reload()?;
+    let amount = ctx.accounts.token_vault.amount.checked_sub(balance_before).ok_or(ErrorCode::MathUnderflow)?;
+    let unstake = liquid_staking_pool.buffer_unstake;
+    liquid_staking_pool.buffer_unstake = Pubkey::default();
+    liquid_staking_pool.buffer_unstake_amount = 0;
+
+    emit_cpi!(BufferReplenished {
+        pool: liquid_staking_pool.key(),
+        cranker: ctx.accounts.cranker.key(),
+        unstake,
+        amount,
+        buffer: liquid_staking_pool.instant_unstake_buffer(ctx.accounts.token_vault.amount),
+    });
+    Ok(())
+}
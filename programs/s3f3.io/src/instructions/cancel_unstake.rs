@@ -8,19 +8,30 @@ use stake_for_fee_interface::{
     CancelUnstakeAccounts,
 };
 use solana_program::{instruction::Instruction, program::invoke_signed};
-use anchor_spl::token_interface::{Mint, TokenAccount};
-use solana_address_lookup_table_program::state::AddressLookupTable;
+use anchor_spl::{
+    metadata::Metadata,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
 
 use crate::{
+    states::config::Config,
     states::pool::{LiquidStakingPool, LiquidStakingPoolStatusBitIndex},
     states::unstake_request::LstUnstakeRequest,
+    states::staker_position::{StakerPosition, STAKER_POSITION_SEED},
     error::ErrorCode,
-    utils::token,
+    events::UnstakeCancelled,
+    utils::{lookup_table, math, token},
     AUTH_SEED,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CancelUnstakeCpi<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
     #[account(mut)]
     pub staker: Signer<'info>,
     #[account(mut)]
@@ -53,7 +64,8 @@ pub struct CancelUnstakeCpi<'info> {
     pub staker_liquid_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
-        constraint = lst_unstake_request.owner == staker.key() @ ErrorCode::InvalidUnstakeRequestOwner,
+        close = staker,
+        constraint = lst_unstake_request.pool == liquid_staking_pool.key() @ ErrorCode::NotApproved,
         constraint = lst_unstake_request.unstake == unstake.key() @ ErrorCode::InvalidUnstakeRequestUnstake,
     )]
     pub lst_unstake_request: Box<Account<'info, LstUnstakeRequest>>,
@@ -66,38 +78,101 @@ pub struct CancelUnstakeCpi<'info> {
     /// CHECK:
     pub cpi_program: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vault.key() == liquid_staking_pool.vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub vault: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = stake_token_vault.key() == liquid_staking_pool.stake_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub stake_token_vault: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = quote_token_vault.key() == liquid_staking_pool.quote_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub quote_token_vault: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = top_staker_list.key() == liquid_staking_pool.top_staker_list @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub top_staker_list: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = full_balance_list.key() == liquid_staking_pool.full_balance_list @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub full_balance_list: UncheckedAccount<'info>,
     /// CHECK: StakeEscrow is handled by CPI
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = stake_escrow.key() == liquid_staking_pool.escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub stake_escrow: UncheckedAccount<'info>,
     /// CHECK:
     #[account(mut)]
     pub smallest_stake_escrow: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = fee_pool.key() == liquid_staking_pool.fee_pool @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub fee_pool: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = lp_mint.key() == liquid_staking_pool.lp_mint @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = lock_escrow.key() == liquid_staking_pool.lock_escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub lock_escrow: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = escrow_vault.key() == liquid_staking_pool.escrow_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub escrow_vault: UncheckedAccount<'info>,
+    /// Position of the request's original staker, who may not be the signing ticket holder
+    #[account(
+        mut,
+        seeds = [
+            STAKER_POSITION_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+            lst_unstake_request.owner.as_ref(),
+        ],
+        bump = staker_position.bump,
+    )]
+    pub staker_position: Option<Box<Account<'info, StakerPosition>>>,
+    /// Required when `lst_unstake_request` has a ticket, which the signer must hold
+    #[account(
+        mut,
+        address = lst_unstake_request.ticket_mint @ ErrorCode::InvalidUnstakeTicket,
+    )]
+    pub ticket_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(
+        mut,
+        constraint = staker_ticket_account.mint == lst_unstake_request.ticket_mint @ ErrorCode::InvalidUnstakeTicket,
+        constraint = staker_ticket_account.owner == staker.key() @ ErrorCode::InvalidUnstakeRequestOwner,
+        constraint = staker_ticket_account.amount == 1 @ ErrorCode::InvalidUnstakeRequestOwner,
+    )]
+    pub staker_ticket_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// CHECK: validated by the token metadata program on burn
+    #[account(mut)]
+    pub ticket_metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by the token metadata program on burn
+    #[account(mut)]
+    pub ticket_edition: Option<UncheckedAccount<'info>>,
+    /// Required with the ticket accounts
+    pub metadata_program: Option<Program<'info, Metadata>>,
+    pub ticket_token_program: Option<Program<'info, Token>>,
     /// CHECK:
     pub token_program: UncheckedAccount<'info>,
     /// CHECK:
@@ -130,9 +205,34 @@ pub fn cancel_unstake_ix_with_program_id(
 
 pub fn cancel_unstake_cpi<'info>(
     ctx: Context<'_, '_, '_, 'info, CancelUnstakeCpi<'info>>,
+    min_liquid_out: u64,
 ) -> Result<()> {
+    let ticket_accounts = match (
+        &ctx.accounts.ticket_mint,
+        &ctx.accounts.staker_ticket_account,
+        &ctx.accounts.ticket_metadata,
+        &ctx.accounts.ticket_edition,
+        &ctx.accounts.ticket_token_program,
+        &ctx.accounts.metadata_program,
+    ) {
+        (Some(mint), Some(token), Some(metadata), Some(edition), Some(token_program), Some(metadata_program)) => Some([
+            mint.to_account_info(),
+            token.to_account_info(),
+            metadata.to_account_info(),
+            edition.to_account_info(),
+            token_program.to_account_info(),
+            metadata_program.to_account_info(),
+        ]),
+        _ => None,
+    };
+    token::settle_unstake_ticket(
+        &ctx.accounts.lst_unstake_request,
+        ctx.accounts.staker.to_account_info(),
+        ticket_accounts,
+    )?;
+
     let liquid_staking_pool = &mut ctx.accounts.liquid_staking_pool;
-    if !liquid_staking_pool.get_status_by_bit(LiquidStakingPoolStatusBitIndex::Deposit) {
+    if !liquid_staking_pool.get_status_by_bit(LiquidStakingPoolStatusBitIndex::CancelUnstake) {
         return err!(ErrorCode::NotApproved);
     }
 
@@ -163,13 +263,11 @@ pub fn cancel_unstake_cpi<'info>(
             return err!(ErrorCode::AccountDeserializeFailed);
         }
     }
-    if liquid_staking_pool.lut != ctx.accounts.lookup_table.key() {
-        return err!(ErrorCode::InvalidLookupTable);
-    }
-    let alt_bytes = ctx.accounts.lookup_table.try_borrow_data()?;
-    let lookup_table: AddressLookupTable = AddressLookupTable::deserialize(&alt_bytes).unwrap();
-    let lookup_table_len = lookup_table.addresses.len();
-    let address_lookup_table_account_infos: &[AccountInfo] = ctx.remaining_accounts.get(..lookup_table_len).ok_or(ErrorCode::InvalidLookupTable)?;
+    let (address_lookup_table_account_infos, leftover_infos) = lookup_table::split_lookup_table_accounts(
+        &ctx.accounts.lookup_table,
+        &liquid_staking_pool.lut,
+        ctx.remaining_accounts,
+    )?;
     
     if ctx.accounts.cpi_program.key() != stake_for_fee_id() {
         return err!(ErrorCode::InvalidCpiProgram);    
@@ -203,7 +301,6 @@ pub fn cancel_unstake_cpi<'info>(
         program: &ctx.accounts.cpi_program.to_account_info(),
     };
     let keys: CancelUnstakeKeys = accounts_cpi.into();
-    let leftover_infos = &ctx.remaining_accounts[lookup_table_len..];
     if leftover_infos.len() > 3 {
         return err!(ErrorCode::InvalidNumberOfAccounts);
     }
@@ -240,7 +337,8 @@ pub fn cancel_unstake_cpi<'info>(
     all_infos.extend_from_slice(leftover_infos);
     invoke_signed(&ix, &all_infos, &[&[crate::AUTH_SEED.as_bytes(), &[liquid_staking_pool.auth_bump]]])?;
     
-    let lamports  = ctx.accounts.authority.lamports();
+    // Refund only the closed unstake account's rent, the authority also holds rent of other unstakes
+    let lamports = Rent::get()?.minimum_balance(8 + std::mem::size_of::<UnstakeAccount>());
     let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
         &ctx.accounts.authority.key(),
         &ctx.accounts.staker.key(),
@@ -256,31 +354,15 @@ pub fn cancel_unstake_cpi<'info>(
         &[&[crate::AUTH_SEED.as_bytes(), &[liquid_staking_pool.auth_bump]]]
     )?;
 
-    let liquid_supply = liquid_staking_pool.liquid_supply;
-    let mut liquid_amount_out = 0;
-    if liquid_supply != 0 {
-        let staked_u128 = (ctx.accounts.token_vault.amount as u128)
-            .checked_add(lst_pool_staked_amount as u128).unwrap().checked_sub(liquid_staking_pool.protocol_fees_token as u128)
-            .ok_or(ErrorCode::OverflowError)?; 
-        // If you prefer using `?`
-        // staked_full / amount = liq_sup / liquid_amount_out_u128
-        // liquid_amount_out_u128 =  liq_sup * amount / staked_full
-        // Multiply in 128-bit space
-        let scaled_u128 = (liquid_supply as u128)
-            .checked_mul(cancel_amount as u128)
-            .ok_or(ErrorCode::OverflowError)?;
-        let liquid_amount_out_u128 = scaled_u128
-            .checked_div(staked_u128)
-            .ok_or(ErrorCode::DivisionError)?;
-
-        if liquid_amount_out_u128 > u64::MAX as u128 {
-            return err!(ErrorCode::OverflowError);
-        }
-        liquid_amount_out = liquid_amount_out_u128 as u64;
-    } else {
-        liquid_amount_out = cancel_amount;
+    let total_backing = liquid_staking_pool.total_backing(ctx.accounts.token_vault.amount, lst_pool_staked_amount)?;
+    let liquid_amount_out = math::token_to_lst(cancel_amount, total_backing, liquid_staking_pool.liquid_supply)?;
+    if liquid_amount_out < min_liquid_out {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+    liquid_staking_pool.liquid_supply = liquid_staking_pool.liquid_supply.checked_add(liquid_amount_out).ok_or(ErrorCode::OverflowError)?;
+    if let Some(staker_position) = &mut ctx.accounts.staker_position {
+        staker_position.record_lst_minted(liquid_amount_out)?;
     }
-    liquid_staking_pool.liquid_supply = liquid_staking_pool.liquid_supply.checked_add(liquid_amount_out).unwrap();
 
     token::token_mint_to(
         ctx.accounts.authority.to_account_info(),
@@ -291,5 +373,14 @@ pub fn cancel_unstake_cpi<'info>(
         &[&[crate::AUTH_SEED.as_bytes(), &[liquid_staking_pool.auth_bump]]],
     )?;
 
+    emit_cpi!(UnstakeCancelled {
+        pool: liquid_staking_pool.key(),
+        staker: ctx.accounts.staker.key(),
+        lst_unstake_request: ctx.accounts.lst_unstake_request.key(),
+        unstake: ctx.accounts.unstake.key(),
+        token_amount: cancel_amount,
+        liquid_amount_out,
+        liquid_supply: liquid_staking_pool.liquid_supply,
+    });
     Ok(())
 }
\ No newline at end of file
@@ -9,20 +9,31 @@ use stake_for_fee_interface::{
     ClaimFeeIxArgs
 };
 use anchor_spl::token_interface::{Mint, TokenAccount};
-use solana_address_lookup_table_program::state::AddressLookupTable;
 use solana_program::{instruction::Instruction, program::invoke_signed};
 
 use crate::{
+    states::config::Config,
     states::pool::{LiquidStakingPool, LiquidStakingPoolStatusBitIndex},
+    states::rate_history::{RateHistory, RATE_HISTORY_SEED},
     error::ErrorCode,
+    events::FeesClaimed,
+    utils::{lookup_table, math},
     AUTH_SEED,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ClaimFeeCpi<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
     #[account(mut)]
     pub staker: Signer<'info>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = !liquid_staking_pool.rate_history_initialized || rate_history.is_some() @ ErrorCode::RateHistoryRequired,
+    )]
     pub liquid_staking_pool: Box<Account<'info, LiquidStakingPool>>,
     /// CHECK
     #[account(
@@ -49,38 +60,77 @@ pub struct ClaimFeeCpi<'info> {
     /// CHECK:
     pub cpi_program: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vault.key() == liquid_staking_pool.vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub vault: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = stake_token_vault.key() == liquid_staking_pool.stake_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub stake_token_vault: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = quote_token_vault.key() == liquid_staking_pool.quote_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub quote_token_vault: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = top_staker_list.key() == liquid_staking_pool.top_staker_list @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub top_staker_list: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = full_balance_list.key() == liquid_staking_pool.full_balance_list @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub full_balance_list: UncheckedAccount<'info>,
     /// CHECK: StakeEscrow is handled by CPI
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = stake_escrow.key() == liquid_staking_pool.escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub stake_escrow: UncheckedAccount<'info>,
     /// CHECK:
     #[account(mut)]
     pub smallest_stake_escrow: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = fee_pool.key() == liquid_staking_pool.fee_pool @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub fee_pool: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = lp_mint.key() == liquid_staking_pool.lp_mint @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = lock_escrow.key() == liquid_staking_pool.lock_escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub lock_escrow: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = escrow_vault.key() == liquid_staking_pool.escrow_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub escrow_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            RATE_HISTORY_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+        ],
+        bump = rate_history.bump,
+    )]
+    pub rate_history: Option<Box<Account<'info, RateHistory>>>,
     /// CHECK:
     pub token_program: UncheckedAccount<'info>,
     /// CHECK:
@@ -119,7 +169,7 @@ pub fn claim_fees_cpi<'info>(
 ) -> Result<()> {
     msg!("========= S3F3.io =========");
     let liquid_staking_pool = &mut ctx.accounts.liquid_staking_pool;
-    if !liquid_staking_pool.get_status_by_bit(LiquidStakingPoolStatusBitIndex::Deposit) {
+    if !liquid_staking_pool.get_status_by_bit(LiquidStakingPoolStatusBitIndex::ClaimFees) {
         return err!(ErrorCode::NotApproved);
     }
     let mut prev_lst_pool_staked_amount = 0;
@@ -136,13 +186,11 @@ pub fn claim_fees_cpi<'info>(
             return err!(ErrorCode::AccountDeserializeFailed);
         }
     }
-    if liquid_staking_pool.lut != ctx.accounts.lookup_table.key() {
-        return err!(ErrorCode::InvalidLookupTable);
-    }
-    let alt_bytes = ctx.accounts.lookup_table.try_borrow_data()?;
-    let lookup_table: AddressLookupTable = AddressLookupTable::deserialize(&alt_bytes).unwrap();
-    let lookup_table_len = lookup_table.addresses.len();
-    let address_lookup_table_account_infos: &[AccountInfo] = ctx.remaining_accounts.get(..lookup_table_len).ok_or(ErrorCode::InvalidLookupTable)?;
+    let (address_lookup_table_account_infos, leftover_infos) = lookup_table::split_lookup_table_accounts(
+        &ctx.accounts.lookup_table,
+        &liquid_staking_pool.lut,
+        ctx.remaining_accounts,
+    )?;
     
     if ctx.accounts.cpi_program.key() != stake_for_fee_id() {
         return err!(ErrorCode::InvalidCpiProgram);    
@@ -182,8 +230,8 @@ pub fn claim_fees_cpi<'info>(
         max_fee: 18446744073709551615,
     };
 
+    let prev_quote_vault_amount = ctx.accounts.liquid_pool_quote_token_vault.amount;
     let keys: ClaimFeeKeys = accounts_cpi.into();
-    let leftover_infos = &ctx.remaining_accounts[lookup_table_len..];
     if leftover_infos.len() > 3 {
         return err!(ErrorCode::InvalidNumberOfAccounts);
     }
@@ -233,9 +281,36 @@ pub fn claim_fees_cpi<'info>(
         }
     }
 
-    let change_amount = current_lst_pool_staked_amount.checked_sub(prev_lst_pool_staked_amount).unwrap();
-    let fee_amount = change_amount.checked_mul(liquid_staking_pool.reward_fee_rate).unwrap().checked_div(100).unwrap();
-    liquid_staking_pool.protocol_fees_token = liquid_staking_pool.protocol_fees_token.checked_add(fee_amount).unwrap();
+    let change_amount = current_lst_pool_staked_amount.checked_sub(prev_lst_pool_staked_amount).ok_or(ErrorCode::EscrowBalanceDecreased)?;
+    let fee_amount = math::fee_amount(change_amount, liquid_staking_pool.reward_fee_rate)?;
+    liquid_staking_pool.protocol_fees_token = liquid_staking_pool.protocol_fees_token.checked_add(fee_amount).ok_or(ErrorCode::OverflowError)?;
+
+    let total_backing = liquid_staking_pool.total_backing(ctx.accounts.token_vault.amount, current_lst_pool_staked_amount)?;
+    ctx.accounts.liquid_pool_quote_token_vault.reload()?;
+    let quote_amount_change = ctx.accounts.liquid_pool_quote_token_vault.amount
+        .checked_sub(prev_quote_vault_amount)
+        .ok_or(ErrorCode::MathUnderflow)?;
+    let quote_protocol_fee = math::fee_amount(quote_amount_change, liquid_staking_pool.reward_fee_rate)?;
+    liquid_staking_pool.protocol_fees_quote = liquid_staking_pool.protocol_fees_quote.checked_add(quote_protocol_fee).ok_or(ErrorCode::OverflowError)?;
+    liquid_staking_pool.accrue_quote_rewards(ctx.accounts.liquid_pool_quote_token_vault.amount)?;
+    liquid_staking_pool.quote_fee_claims = liquid_staking_pool.quote_fee_claims.checked_add(1).ok_or(ErrorCode::OverflowError)?;
+
+    if let Some(rate_history) = &mut ctx.accounts.rate_history {
+        rate_history.record(&Clock::get()?, total_backing, liquid_staking_pool.liquid_supply);
+    }
+
+    emit_cpi!(FeesClaimed {
+        pool: liquid_staking_pool.key(),
+        claimer: ctx.accounts.staker.key(),
+        staked_amount_change: change_amount,
+        protocol_fee: fee_amount,
+        protocol_fees_token: liquid_staking_pool.protocol_fees_token,
+        quote_amount_change,
+        quote_protocol_fee,
+        protocol_fees_quote: liquid_staking_pool.protocol_fees_quote,
+        total_backing,
+        liquid_supply: liquid_staking_pool.liquid_supply,
+    });
 
     Ok(())
 }
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::states::config::Config;
+use crate::states::pool::LiquidStakingPool;
+use crate::states::quote_reward_checkpoint::{QuoteRewardCheckpoint, QUOTE_REWARD_CHECKPOINT_SEED};
+use crate::events::QuoteRewardsClaimed;
+use crate::error::ErrorCode;
+use crate::utils::token;
+use crate::AUTH_SEED;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimQuoteRewards<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub pool: Box<Account<'info, LiquidStakingPool>>,
+    /// CHECK: authority
+    #[account(
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            QUOTE_REWARD_CHECKPOINT_SEED.as_bytes(),
+            pool.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump = checkpoint.bump,
+    )]
+    pub checkpoint: Box<Account<'info, QuoteRewardCheckpoint>>,
+    #[account(
+        mut,
+        constraint = quote_vault.key() == pool.quote_vault_account @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        constraint = quote_mint.key() == quote_vault.mint @ ErrorCode::InvalidInput,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = owner_quote_token_vault.mint == quote_vault.mint @ ErrorCode::InvalidInput,
+    )]
+    pub owner_quote_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK:
+    pub token_program: UncheckedAccount<'info>,
+}
+
+pub fn claim_quote_rewards(ctx: Context<ClaimQuoteRewards>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.accrue_quote_rewards(ctx.accounts.quote_vault.amount)?;
+    let checkpoint = &mut ctx.accounts.checkpoint;
+    let forfeited = checkpoint.settle(pool.quote_reward_per_lst, pool.quote_fee_claims)?;
+    pool.release_quote_rewards(forfeited)?;
+
+    let amount = checkpoint.accrued_quote;
+    checkpoint.accrued_quote = 0;
+    checkpoint.total_claimed_quote = checkpoint.total_claimed_quote.checked_add(amount).ok_or(ErrorCode::OverflowError)?;
+    pool.quote_rewards_reserved = pool.quote_rewards_reserved.checked_sub(amount).ok_or(ErrorCode::MathUnderflow)?;
+
+    token::transfer_from_pool_vault_to_user(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.quote_vault.to_account_info(),
+        ctx.accounts.owner_quote_token_vault.to_account_info(),
+        ctx.accounts.quote_mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        amount,
+        ctx.accounts.quote_mint.decimals,
+        &[&[crate::AUTH_SEED.as_bytes(), &[pool.auth_bump]]],
+    )?;
+
+    emit_cpi!(QuoteRewardsClaimed {
+        pool: pool.key(),
+        owner: ctx.accounts.owner.key(),
+        amount,
+        quote_rewards_reserved: pool.quote_rewards_reserved,
+    });
+    Ok(())
+}
@@ -0,0 +1,401 @@
+use anchor_lang::prelude::*;
+use stake_for_fee_interface::{
+    accounts::StakeEscrowAccount,
+    id as stake_for_fee_id,
+    StakeAccounts,
+    StakeIxArgs,
+    STAKE_IX_ACCOUNTS_LEN,
+    StakeKeys,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use solana_program::{instruction::Instruction, program::invoke_signed};
+use crate::{
+    instructions::stake::stake_ix_with_program_id,
+    states::config::Config,
+    states::pool::{LiquidStakingPool, LiquidStakingPoolStatusBitIndex},
+    states::rate_history::{RateHistory, RATE_HISTORY_SEED},
+    error::ErrorCode,
+    events::Compounded,
+    utils::{
+        lookup_table,
+        math,
+        meteora::{self, AMM_POOL_A_VAULT_LP_OFFSET, AMM_POOL_A_VAULT_OFFSET, AMM_POOL_B_VAULT_LP_OFFSET, AMM_POOL_B_VAULT_OFFSET, AMM_SWAP_DISCRIMINATOR},
+    },
+    AUTH_SEED,
+};
+
+pub const SWAP_IX_ACCOUNTS_LEN: usize = 15;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CompoundCpi<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        constraint = config.creator_authority == caller.key() || liquid_staking_pool.pool_creator == caller.key() @ ErrorCode::NotApproved,
+        constraint = liquid_staking_pool.swap_program != Pubkey::default() @ ErrorCode::CompoundRouteNotSet,
+        constraint = liquid_staking_pool.max_compound_slippage_bps != 0 @ ErrorCode::InvalidCompoundSlippage,
+        constraint = !liquid_staking_pool.rate_history_initialized || rate_history.is_some() @ ErrorCode::RateHistoryRequired,
+    )]
+    pub liquid_staking_pool: Box<Account<'info, LiquidStakingPool>>,
+    /// CHECK
+    #[account(
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = liquid_staking_pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = token_vault.key() == liquid_staking_pool.token_vault_account @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = liquid_pool_quote_token_vault.key() == liquid_staking_pool.quote_vault_account @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub liquid_pool_quote_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: checked against the pool's compound route
+    #[account(
+        constraint = swap_program.key() == liquid_staking_pool.swap_program @ ErrorCode::CompoundRouteNotSet,
+    )]
+    pub swap_program: UncheckedAccount<'info>,
+    /// CHECK: checked against the pool's compound route
+    #[account(
+        mut,
+        constraint = swap_pool.key() == liquid_staking_pool.swap_pool @ ErrorCode::CompoundRouteNotSet,
+    )]
+    pub swap_pool: UncheckedAccount<'info>,
+    /// CHECK: validated by the swap program
+    #[account(mut)]
+    pub swap_a_vault: UncheckedAccount<'info>,
+    /// CHECK: validated by the swap program
+    #[account(mut)]
+    pub swap_b_vault: UncheckedAccount<'info>,
+    /// CHECK: validated by the swap program
+    #[account(mut)]
+    pub swap_a_token_vault: UncheckedAccount<'info>,
+    /// CHECK: validated by the swap program
+    #[account(mut)]
+    pub swap_b_token_vault: UncheckedAccount<'info>,
+    /// CHECK: validated by the swap program
+    #[account(mut)]
+    pub swap_a_vault_lp_mint: UncheckedAccount<'info>,
+    /// CHECK: validated by the swap program
+    #[account(mut)]
+    pub swap_b_vault_lp_mint: UncheckedAccount<'info>,
+    /// CHECK: validated by the swap program
+    #[account(mut)]
+    pub swap_a_vault_lp: UncheckedAccount<'info>,
+    /// CHECK: validated by the swap program
+    #[account(mut)]
+    pub swap_b_vault_lp: UncheckedAccount<'info>,
+    /// CHECK: validated by the swap program
+    #[account(mut)]
+    pub swap_protocol_token_fee: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: the account will be validated by the lookup table program
+    pub lookup_table: AccountInfo<'info>,
+    /// CHECK:
+    pub cpi_program: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = vault.key() == liquid_staking_pool.vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = stake_token_vault.key() == liquid_staking_pool.stake_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub stake_token_vault: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = quote_token_vault.key() == liquid_staking_pool.quote_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub quote_token_vault: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = top_staker_list.key() == liquid_staking_pool.top_staker_list @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub top_staker_list: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = full_balance_list.key() == liquid_staking_pool.full_balance_list @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub full_balance_list: UncheckedAccount<'info>,
+    /// CHECK: StakeEscrow is handled by CPI
+    #[account(
+        mut,
+        constraint = stake_escrow.key() == liquid_staking_pool.escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub stake_escrow: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut)]
+    pub smallest_stake_escrow: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = fee_pool.key() == liquid_staking_pool.fee_pool @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub fee_pool: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = lp_mint.key() == liquid_staking_pool.lp_mint @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = lock_escrow.key() == liquid_staking_pool.lock_escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub lock_escrow: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = escrow_vault.key() == liquid_staking_pool.escrow_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub escrow_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            RATE_HISTORY_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+        ],
+        bump = rate_history.bump,
+    )]
+    pub rate_history: Option<Box<Account<'info, RateHistory>>>,
+    /// CHECK:
+    pub token_program: UncheckedAccount<'info>,
+    /// CHECK:
+    pub system_program: UncheckedAccount<'info>,
+}
+
+impl<'info> CompoundCpi<'info> {
+    fn stake_escrow_amount(&self) -> Result<u64> {
+        let data_ref = self.stake_escrow.try_borrow_data()?;
+        let stake_escrow_account = StakeEscrowAccount::deserialize(&data_ref)?;
+        let stake_escrow = &stake_escrow_account.0;
+        if stake_escrow.owner != self.authority.key() {
+            return err!(ErrorCode::AccountDeserializeFailed);
+        }
+        Ok(stake_escrow.stake_amount)
+    }
+
+    /// Quote and staking token reserves of the swap pool, read from its vaults
+    fn swap_reserves(&self) -> Result<(u64, u64)> {
+        let (a_mint, a_reserve) = meteora::amm_pool_reserve(
+            &self.swap_pool,
+            &self.swap_a_vault,
+            &self.swap_a_vault_lp,
+            &self.swap_a_vault_lp_mint,
+            AMM_POOL_A_VAULT_OFFSET,
+            AMM_POOL_A_VAULT_LP_OFFSET,
+        )?;
+        let (b_mint, b_reserve) = meteora::amm_pool_reserve(
+            &self.swap_pool,
+            &self.swap_b_vault,
+            &self.swap_b_vault_lp,
+            &self.swap_b_vault_lp_mint,
+            AMM_POOL_B_VAULT_OFFSET,
+            AMM_POOL_B_VAULT_LP_OFFSET,
+        )?;
+        let quote_mint = self.liquid_pool_quote_token_vault.mint;
+        let token_mint = self.token_vault.mint;
+        if a_mint == quote_mint && b_mint == token_mint {
+            Ok((a_reserve, b_reserve))
+        } else if a_mint == token_mint && b_mint == quote_mint {
+            Ok((b_reserve, a_reserve))
+        } else {
+            err!(ErrorCode::InvalidMeteoraAccount)
+        }
+    }
+}
+
+/// Builds a dynamic AMM `swap` from the pool quote vault into the pool token vault
+pub fn swap_ix(accounts: &CompoundCpi, vault_program: &AccountInfo, in_amount: u64, minimum_out_amount: u64) -> Instruction {
+    let mut data = Vec::with_capacity(24);
+    data.extend_from_slice(&AMM_SWAP_DISCRIMINATOR);
+    data.extend_from_slice(&in_amount.to_le_bytes());
+    data.extend_from_slice(&minimum_out_amount.to_le_bytes());
+    Instruction {
+        program_id: accounts.swap_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.swap_pool.key(), false),
+            AccountMeta::new(accounts.liquid_pool_quote_token_vault.key(), false),
+            AccountMeta::new(accounts.token_vault.key(), false),
+            AccountMeta::new(accounts.swap_a_vault.key(), false),
+            AccountMeta::new(accounts.swap_b_vault.key(), false),
+            AccountMeta::new(accounts.swap_a_token_vault.key(), false),
+            AccountMeta::new(accounts.swap_b_token_vault.key(), false),
+            AccountMeta::new(accounts.swap_a_vault_lp_mint.key(), false),
+            AccountMeta::new(accounts.swap_b_vault_lp_mint.key(), false),
+            AccountMeta::new(accounts.swap_a_vault_lp.key(), false),
+            AccountMeta::new(accounts.swap_b_vault_lp.key(), false),
+            AccountMeta::new(accounts.swap_protocol_token_fee.key(), false),
+            AccountMeta::new_readonly(accounts.authority.key(), true),
+            AccountMeta::new_readonly(vault_program.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+        ],
+        data,
+    }
+}
+
+/// Admin only: swaps the pool's quote balance, net of protocol fees and unclaimed quote rewards, into the staking
+/// token and stakes it into the escrow without minting LST, raising the exchange rate. While quote rewards are
+/// distributed, only the quote held back from checkpoints is compounded.
+/// The reserve quote is read in the same transaction and can be moved by a sandwich, so the caller is trusted to pass a
+/// `min_token_out` from an off-chain price, and each call swaps at most `MAX_COMPOUND_RESERVE_BPS` of the quote reserve.
+/// The swap must also fill within the pool's slippage bound of the AMM reserve quote
+pub fn compound_cpi<'info>(
+    ctx: Context<'_, '_, '_, 'info, CompoundCpi<'info>>,
+    min_token_out: u64,
+) -> Result<()> {
+    msg!("========= S3F3.io =========");
+    if !ctx.accounts.liquid_staking_pool.get_status_by_bit(LiquidStakingPoolStatusBitIndex::Compound) {
+        return err!(ErrorCode::NotApproved);
+    }
+    if ctx.accounts.cpi_program.key() != stake_for_fee_id() {
+        return err!(ErrorCode::InvalidCpiProgram);
+    }
+    let auth_bump = ctx.accounts.liquid_staking_pool.auth_bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[crate::AUTH_SEED.as_bytes(), &[auth_bump]]];
+
+    let compoundable = ctx.accounts.liquid_staking_pool.compoundable_quote(ctx.accounts.liquid_pool_quote_token_vault.amount)?;
+    if compoundable == 0 {
+        return err!(ErrorCode::NothingToCompound);
+    }
+
+    let (address_lookup_table_account_infos, leftover_infos) = lookup_table::split_lookup_table_accounts(
+        &ctx.accounts.lookup_table,
+        &ctx.accounts.liquid_staking_pool.lut,
+        ctx.remaining_accounts,
+    )?;
+    if leftover_infos.len() > 3 {
+        return err!(ErrorCode::InvalidNumberOfAccounts);
+    }
+    let vault_program = &address_lookup_table_account_infos[9];
+
+    let (quote_reserve, token_reserve) = ctx.accounts.swap_reserves()?;
+    let swap_amount = math::compound_swap_amount(compoundable, quote_reserve)?;
+    if swap_amount == 0 {
+        return err!(ErrorCode::NothingToCompound);
+    }
+    let reserve_min_out = math::compound_min_out(
+        swap_amount,
+        quote_reserve,
+        token_reserve,
+        ctx.accounts.liquid_staking_pool.max_compound_slippage_bps,
+    )?;
+    let min_token_out = min_token_out.max(reserve_min_out);
+
+    let quote_before = ctx.accounts.liquid_pool_quote_token_vault.amount;
+    let token_before = ctx.accounts.token_vault.amount;
+    let ix = swap_ix(ctx.accounts, vault_program, swap_amount, min_token_out);
+    let swap_infos: [AccountInfo<'info>; SWAP_IX_ACCOUNTS_LEN] = [
+        ctx.accounts.swap_pool.to_account_info(),
+        ctx.accounts.liquid_pool_quote_token_vault.to_account_info(),
+        ctx.accounts.token_vault.to_account_info(),
+        ctx.accounts.swap_a_vault.to_account_info(),
+        ctx.accounts.swap_b_vault.to_account_info(),
+        ctx.accounts.swap_a_token_vault.to_account_info(),
+        ctx.accounts.swap_b_token_vault.to_account_info(),
+        ctx.accounts.swap_a_vault_lp_mint.to_account_info(),
+        ctx.accounts.swap_b_vault_lp_mint.to_account_info(),
+        ctx.accounts.swap_a_vault_lp.to_account_info(),
+        ctx.accounts.swap_b_vault_lp.to_account_info(),
+        ctx.accounts.swap_protocol_token_fee.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        vault_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    ];
+    let mut all_swap_infos = Vec::with_capacity(SWAP_IX_ACCOUNTS_LEN + 1);
+    all_swap_infos.extend_from_slice(&swap_infos);
+    all_swap_infos.push(ctx.accounts.swap_program.to_account_info());
+    invoke_signed(&ix, &all_swap_infos, signer_seeds)?;
+
+    ctx.accounts.liquid_pool_quote_token_vault.reload()?;
+    ctx.accounts.token_vault.reload()?;
+    // Measure the swap from balances rather than trusting the swap program
+    let quote_in = quote_before
+        .checked_sub(ctx.accounts.liquid_pool_quote_token_vault.amount)
+        .ok_or(ErrorCode::MathUnderflow)?;
+    if quote_in > swap_amount {
+        return err!(ErrorCode::CompoundOverspent);
+    }
+    let liquid_staking_pool = &mut ctx.accounts.liquid_staking_pool;
+    liquid_staking_pool.quote_compound_pending = liquid_staking_pool.quote_compound_pending.saturating_sub(quote_in);
+    let token_out = ctx.accounts.token_vault.amount
+        .checked_sub(token_before)
+        .ok_or(ErrorCode::MathUnderflow)?;
+    if token_out < min_token_out || token_out == 0 {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+
+    let accounts_cpi: StakeAccounts<'_, '_> = StakeAccounts {
+        vault: &ctx.accounts.vault.to_account_info(),
+        stake_token_vault: &ctx.accounts.stake_token_vault.to_account_info(),
+        quote_token_vault: &ctx.accounts.quote_token_vault.to_account_info(),
+        top_staker_list: &ctx.accounts.top_staker_list.to_account_info(),
+        full_balance_list: &ctx.accounts.full_balance_list.to_account_info(),
+        stake_escrow: &ctx.accounts.stake_escrow.to_account_info(),
+        smallest_stake_escrow: &ctx.accounts.smallest_stake_escrow.to_account_info(),
+        user_stake_token: &ctx.accounts.token_vault.to_account_info(),
+        owner: &ctx.accounts.authority.to_account_info(),
+        pool: &ctx.accounts.fee_pool.to_account_info(),
+        lp_mint: &ctx.accounts.lp_mint.to_account_info(),
+        lock_escrow: &ctx.accounts.lock_escrow.to_account_info(),
+        escrow_vault: &ctx.accounts.escrow_vault.to_account_info(),
+        a_vault: &address_lookup_table_account_infos[0],
+        b_vault: &address_lookup_table_account_infos[1],
+        a_vault_lp: &address_lookup_table_account_infos[2],
+        b_vault_lp: &address_lookup_table_account_infos[3],
+        a_vault_lp_mint: &address_lookup_table_account_infos[4],
+        b_vault_lp_mint: &address_lookup_table_account_infos[5],
+        a_token_vault: &address_lookup_table_account_infos[6],
+        b_token_vault: &address_lookup_table_account_infos[7],
+        amm_program: &address_lookup_table_account_infos[8],
+        vault_program: &address_lookup_table_account_infos[9],
+        event_authority: &address_lookup_table_account_infos[10],
+        token_program: &ctx.accounts.token_program.to_account_info(),
+        program: &ctx.accounts.cpi_program.to_account_info(),
+    };
+    let keys: StakeKeys = accounts_cpi.into();
+    let ix = stake_ix_with_program_id(ctx.accounts.cpi_program.key(), keys, leftover_infos, StakeIxArgs { amount: token_out })?;
+    let base_infos: [AccountInfo<'info>; STAKE_IX_ACCOUNTS_LEN] = accounts_cpi.into();
+    let mut all_infos = Vec::with_capacity(STAKE_IX_ACCOUNTS_LEN + leftover_infos.len());
+    all_infos.extend_from_slice(&base_infos);
+    all_infos.extend_from_slice(leftover_infos);
+    invoke_signed(&ix, &all_infos, signer_seeds)?;
+
+    ctx.accounts.token_vault.reload()?;
+    let staked_amount = ctx.accounts.stake_escrow_amount()?;
+    let liquid_staking_pool = &ctx.accounts.liquid_staking_pool;
+    let total_backing = liquid_staking_pool.total_backing(ctx.accounts.token_vault.amount, staked_amount)?;
+    let liquid_supply = liquid_staking_pool.liquid_supply;
+    if let Some(rate_history) = &mut ctx.accounts.rate_history {
+        rate_history.record(&Clock::get()?, total_backing, liquid_supply);
+    }
+
+    emit_cpi!(Compounded {
+        pool: ctx.accounts.liquid_staking_pool.key(),
+        caller: ctx.accounts.caller.key(),
+        quote_in,
+        token_out,
+        total_backing,
+        liquid_supply,
+    });
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::states::config::Config;
+use crate::events::ConfigAuthorityTransferred;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptConfigAuthority<'info> {
+    #[account(
+        mut,
+        constraint = config.pending_authority != Pubkey::default() @ ErrorCode::NoPendingAuthority,
+        constraint = config.pending_authority == signer.key() @ ErrorCode::NotApproved,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+}
+
+pub fn accept_config_authority(ctx: Context<AcceptConfigAuthority>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_authority = config.creator_authority;
+    config.creator_authority = config.pending_authority;
+    config.pending_authority = Pubkey::default();
+
+    emit_cpi!(ConfigAuthorityTransferred {
+        config: config.key(),
+        old_authority,
+        new_authority: config.creator_authority,
+    });
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod propose;
+pub use propose::*;
+
+pub mod accept;
+pub use accept::*;
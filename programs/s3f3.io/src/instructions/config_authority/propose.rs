@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::states::config::Config;
+use crate::events::ConfigAuthorityProposed;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeConfigAuthority<'info> {
+    #[account(
+        mut,
+        constraint = config.creator_authority == signer.key() @ ErrorCode::NotApproved,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+}
+
+/// Proposing `Pubkey::default()` cancels a pending transfer.
+pub fn propose_config_authority(ctx: Context<ProposeConfigAuthority>, new_authority: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.pending_authority = new_authority;
+
+    emit_cpi!(ConfigAuthorityProposed {
+        config: config.key(),
+        authority: config.creator_authority,
+        pending_authority: new_authority,
+    });
+    Ok(())
+}
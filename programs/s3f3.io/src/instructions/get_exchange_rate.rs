@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use stake_for_fee_interface::accounts::StakeEscrowAccount;
+use crate::states::pool::LiquidStakingPool;
+use crate::utils::math;
+use crate::error::ErrorCode;
+
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ExchangeRate {
+    pub total_backing: u64,
+    pub liquid_supply: u64,
+    /// Staking token base units per `EXCHANGE_RATE_PRECISION` LST base units
+    pub rate: u64,
+}
+
+#[derive(Accounts)]
+pub struct GetExchangeRate<'info> {
+    pub liquid_staking_pool: Account<'info, LiquidStakingPool>,
+    #[account(
+        constraint = token_vault.key() == liquid_staking_pool.token_vault_account @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: deserialized as a Meteora StakeEscrow below
+    #[account(
+        constraint = stake_escrow.key() == liquid_staking_pool.escrow @ ErrorCode::AccountDeserializeFailed,
+    )]
+    pub stake_escrow: UncheckedAccount<'info>,
+}
+
+pub fn get_exchange_rate(ctx: Context<GetExchangeRate>) -> Result<ExchangeRate> {
+    let staked_amount = {
+        let data_ref = ctx.accounts.stake_escrow.try_borrow_data()?;
+        StakeEscrowAccount::deserialize(&data_ref)?.0.stake_amount
+    };
+    let pool = &ctx.accounts.liquid_staking_pool;
+    let total_backing = pool.total_backing(ctx.accounts.token_vault.amount, staked_amount)?;
+    let rate = math::exchange_rate(total_backing, pool.liquid_supply)?;
+
+    Ok(ExchangeRate {
+        total_backing,
+        liquid_supply: pool.liquid_supply,
+        rate,
+    })
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::states::config::{Config, CONFIG_SEED};
+use crate::events::ConfigInitialized;
+use crate::states::config::{Config, CONFIG_SEED, DEFAULT_MAX_DEPOSIT_FEE_RATE, DEFAULT_MAX_REWARD_FEE_RATE};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeConfig<'info> {
     #[account(
@@ -25,6 +27,13 @@ pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
     let config = &mut ctx.accounts.config;
     config.creator_authority = ctx.accounts.owner.key();
     config.bump = ctx.bumps.config;
+    config.max_deposit_fee_rate = DEFAULT_MAX_DEPOSIT_FEE_RATE;
+    config.max_reward_fee_rate = DEFAULT_MAX_REWARD_FEE_RATE;
+
+    emit_cpi!(ConfigInitialized {
+        config: config.key(),
+        authority: config.creator_authority,
+    });
 
     Ok(())
 }
@@ -1,15 +1,22 @@
 use anchor_lang::prelude::*;
-use stake_for_fee_interface::{InitializeStakeEscrowAccounts};
+use stake_for_fee_interface::{
+    id as stake_for_fee_id,
+    InitializeStakeEscrowAccounts,
+};
 use crate::states::config::Config;
 use crate::states::pool::LiquidStakingPool;
+use crate::events::EscrowInitialized;
+use crate::utils::meteora;
 use crate::AUTH_SEED;
 use crate::error::ErrorCode;
 
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeEscrowCpi<'info> {
     #[account(
-        constraint = config.creator_authority == creator.key()  
+        constraint = config.creator_authority == creator.key(),
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
     )]
     pub config: Box<Account<'info, Config>>,
     #[account(mut)]
@@ -29,9 +36,14 @@ pub struct InitializeEscrowCpi<'info> {
     pub authority: UncheckedAccount<'info>,
     /// CHECK:
     pub cpi_program: UncheckedAccount<'info>,
-    /// CHECK:    
-    #[account(mut)]
+    /// CHECK: deserialized as a Meteora FeeVault below
+    #[account(
+        mut,
+        constraint = vault.key() == pool.vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub vault: UncheckedAccount<'info>,
+    /// CHECK: checked against the FeeVault pool
+    pub fee_pool: UncheckedAccount<'info>,
     /// CHECK:
     #[account(mut)]
     pub escrow: UncheckedAccount<'info>,
@@ -43,7 +55,7 @@ pub struct InitializeEscrowCpi<'info> {
     pub full_balance_list: UncheckedAccount<'info>,
 
     /// CHECK:
-    pub event_authority: UncheckedAccount<'info>,
+    pub cpi_event_authority: UncheckedAccount<'info>,
     /// CHECK:
     pub system_program: UncheckedAccount<'info>,
 }
@@ -54,7 +66,17 @@ pub fn initialize_escrow_cpi(ctx: Context<InitializeEscrowCpi>) -> Result<()> {
         return err!(ErrorCode::EscrowAlreadyInitialized);
     }
     pool.escrow = ctx.accounts.escrow.key();
-    
+
+    if ctx.accounts.cpi_program.key() != stake_for_fee_id() {
+        return err!(ErrorCode::InvalidCpiProgram);
+    }
+    let fee_vault = meteora::record_fee_vault_accounts(pool, &ctx.accounts.vault, &ctx.accounts.fee_pool)?;
+    if fee_vault.top_staker_list != ctx.accounts.top_staker_list.key()
+        || fee_vault.full_balance_list != ctx.accounts.full_balance_list.key()
+    {
+        return err!(ErrorCode::InvalidMeteoraAccount);
+    }
+
     let accounts_cpi = InitializeStakeEscrowAccounts {
         vault: &ctx.accounts.vault.to_account_info(),
         escrow: &ctx.accounts.escrow.to_account_info(),
@@ -63,10 +85,16 @@ pub fn initialize_escrow_cpi(ctx: Context<InitializeEscrowCpi>) -> Result<()> {
         owner: &ctx.accounts.authority.to_account_info(),
         payer: &ctx.accounts.creator.to_account_info(),
         system_program: &ctx.accounts.system_program.to_account_info(),
-        event_authority: &ctx.accounts.event_authority.to_account_info(),
+        event_authority: &ctx.accounts.cpi_event_authority.to_account_info(),
         program: &ctx.accounts.cpi_program.to_account_info(),
     };
     stake_for_fee_interface::instructions::initialize_stake_escrow_invoke_signed(accounts_cpi, &[&[crate::AUTH_SEED.as_bytes(), &[pool.auth_bump]]])?;
 
+    emit_cpi!(EscrowInitialized {
+        pool: pool.key(),
+        vault: ctx.accounts.vault.key(),
+        escrow: pool.escrow,
+    });
+
     Ok(())
 }
\ No newline at end of file
@@ -3,13 +3,17 @@ use anchor_spl::token_interface::{Mint, TokenInterface};
 
 use crate::states::pool::{LiquidStakingPool, LIQUID_STAKING_POOL_SEED, LIQUID_STAKING_POOL_VAULT_SEED, LIQUID_STAKING_POOL_MINT_SEED};
 use crate::states::config::Config;
+use crate::events::PoolInitialized;
 use crate::AUTH_SEED;
+use crate::error::ErrorCode;
 use crate::utils::token::create_token_account;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializePool<'info> {
     #[account(
-        constraint = config.creator_authority == creator.key()  
+        constraint = config.creator_authority == creator.key(),
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
     )]
     pub config: Box<Account<'info, Config>>,
     #[account(mut)]
@@ -112,7 +116,7 @@ pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
     pool.bump = ctx.bumps.pool;
     pool.auth_bump = ctx.bumps.authority;
     pool.status = 0;
-    pool.padding = [0; 64];
+    pool.quote_fee_claims = 0;
     
 
     create_token_account(
@@ -160,6 +164,15 @@ pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
         ][..]],
     )?;
 
-
+    emit_cpi!(PoolInitialized {
+        pool: ctx.accounts.pool.key(),
+        creator: ctx.accounts.creator.key(),
+        vault: ctx.accounts.vault.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        quote_mint: ctx.accounts.quote_mint.key(),
+        liquid_token_mint: ctx.accounts.liquid_token_mint.key(),
+        deposit_fee_rate: ctx.accounts.pool.deposit_fee_rate,
+        reward_fee_rate: ctx.accounts.pool.reward_fee_rate,
+    });
     Ok(())
 }
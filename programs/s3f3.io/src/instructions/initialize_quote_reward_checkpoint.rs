@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::states::config::Config;
+use crate::states::pool::LiquidStakingPool;
+use crate::states::quote_reward_checkpoint::{QuoteRewardCheckpoint, QUOTE_REWARD_CHECKPOINT_SEED};
+use crate::events::QuoteRewardCheckpointInitialized;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeQuoteRewardCheckpoint<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub pool: Account<'info, LiquidStakingPool>,
+    #[account(
+        init,
+        payer = owner,
+        space = QuoteRewardCheckpoint::LEN,
+        seeds = [
+            QUOTE_REWARD_CHECKPOINT_SEED.as_bytes(),
+            pool.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump
+    )]
+    pub checkpoint: Box<Account<'info, QuoteRewardCheckpoint>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_quote_reward_checkpoint(ctx: Context<InitializeQuoteRewardCheckpoint>) -> Result<()> {
+    let checkpoint = &mut ctx.accounts.checkpoint;
+    checkpoint.pool = ctx.accounts.pool.key();
+    checkpoint.owner = ctx.accounts.owner.key();
+    checkpoint.reward_per_lst_paid = ctx.accounts.pool.quote_reward_per_lst;
+    checkpoint.bump = ctx.bumps.checkpoint;
+
+    emit_cpi!(QuoteRewardCheckpointInitialized {
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.owner.key(),
+        checkpoint: ctx.accounts.checkpoint.key(),
+        reward_per_lst_paid: ctx.accounts.pool.quote_reward_per_lst,
+    });
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::states::config::Config;
+use crate::states::pool::LiquidStakingPool;
+use crate::states::rate_history::{RateHistory, RATE_HISTORY_SEED};
+use crate::events::RateHistoryInitialized;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeRateHistory<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = config.creator_authority == payer.key() || pool.pool_creator == payer.key() @ ErrorCode::NotApproved,
+    )]
+    pub pool: Account<'info, LiquidStakingPool>,
+    #[account(
+        init,
+        payer = payer,
+        space = RateHistory::LEN,
+        seeds = [
+            RATE_HISTORY_SEED.as_bytes(),
+            pool.key().as_ref(),
+        ],
+        bump
+    )]
+    pub rate_history: Box<Account<'info, RateHistory>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Once the history exists, `stake`, `claim_fees`, `compound` and `instant_unstake` require it,
+/// so only the pool creator or config authority may switch a pool over
+pub fn initialize_rate_history(ctx: Context<InitializeRateHistory>) -> Result<()> {
+    let rate_history = &mut ctx.accounts.rate_history;
+    rate_history.pool = ctx.accounts.pool.key();
+    rate_history.bump = ctx.bumps.rate_history;
+    rate_history.head = 0;
+    rate_history.len = 0;
+    ctx.accounts.pool.rate_history_initialized = true;
+
+    emit_cpi!(RateHistoryInitialized {
+        pool: ctx.accounts.pool.key(),
+        payer: ctx.accounts.payer.key(),
+        rate_history: ctx.accounts.rate_history.key(),
+    });
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::states::config::Config;
+use crate::states::pool::LiquidStakingPool;
+use crate::states::staker_position::{StakerPosition, STAKER_POSITION_SEED};
+use crate::events::StakerPositionInitialized;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeStakerPosition<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub staker: Signer<'info>,
+    pub pool: Account<'info, LiquidStakingPool>,
+    #[account(
+        init,
+        payer = staker,
+        space = StakerPosition::LEN,
+        seeds = [
+            STAKER_POSITION_SEED.as_bytes(),
+            pool.key().as_ref(),
+            staker.key().as_ref(),
+        ],
+        bump
+    )]
+    pub staker_position: Box<Account<'info, StakerPosition>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_staker_position(ctx: Context<InitializeStakerPosition>) -> Result<()> {
+    let staker_position = &mut ctx.accounts.staker_position;
+    staker_position.pool = ctx.accounts.pool.key();
+    staker_position.owner = ctx.accounts.staker.key();
+    staker_position.bump = ctx.bumps.staker_position;
+
+    emit_cpi!(StakerPositionInitialized {
+        pool: ctx.accounts.pool.key(),
+        owner: ctx.accounts.staker.key(),
+        staker_position: ctx.accounts.staker_position.key(),
+    });
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use stake_for_fee_interface::accounts::StakeEscrowAccount;
+use crate::{
+    states::config::Config,
+    states::pool::{LiquidStakingPool, LiquidStakingPoolStatusBitIndex},
+    states::rate_history::{RateHistory, RATE_HISTORY_SEED},
+    states::staker_position::{StakerPosition, STAKER_POSITION_SEED},
+    error::ErrorCode,
+    events::InstantUnstaked,
+    utils::{math, token},
+    AUTH_SEED,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InstantUnstake<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub staker: Signer<'info>,
+    #[account(
+        mut,
+        constraint = !liquid_staking_pool.rate_history_initialized || rate_history.is_some() @ ErrorCode::RateHistoryRequired,
+    )]
+    pub liquid_staking_pool: Box<Account<'info, LiquidStakingPool>>,
+    /// CHECK
+    #[account(
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = liquid_staking_pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = token_vault.key() == liquid_staking_pool.token_vault_account @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        constraint = token_mint.key() == liquid_staking_pool.token_mint @ ErrorCode::InvalidInput,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = liquid_token_mint.key() == liquid_staking_pool.liquid_token_mint @ ErrorCode::InvalidLiquidStakingTokenMint,
+    )]
+    pub liquid_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = staker_token_vault.owner == staker.key() @ ErrorCode::InvalidLiquidStakingTokenOwner,
+        constraint = staker_token_vault.mint == liquid_staking_pool.token_mint @ ErrorCode::InvalidLiquidStakingTokenMint,
+    )]
+    pub staker_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = staker_liquid_token_vault.mint == liquid_staking_pool.liquid_token_mint @ ErrorCode::InvalidLiquidStakingTokenMint,
+        constraint = staker_liquid_token_vault.owner == staker.key() @ ErrorCode::InvalidLiquidStakingTokenOwner,
+    )]
+    pub staker_liquid_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: StakeEscrow, read for the staked amount
+    #[account(
+        constraint = stake_escrow.key() == liquid_staking_pool.escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub stake_escrow: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            RATE_HISTORY_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+        ],
+        bump = rate_history.bump,
+    )]
+    pub rate_history: Option<Box<Account<'info, RateHistory>>>,
+    #[account(
+        mut,
+        seeds = [
+            STAKER_POSITION_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+            staker.key().as_ref(),
+        ],
+        bump = staker_position.bump,
+    )]
+    pub staker_position: Option<Box<Account<'info, StakerPosition>>>,
+    /// CHECK:
+    pub token_program: UncheckedAccount<'info>,
+}
+
+/// Redeems LST straight from the idle buffer at the current rate minus the instant-exit fee,
+/// skipping the Meteora unstake cooldown
+pub fn instant_unstake(ctx: Context<InstantUnstake>, lst_amount: u64, min_amount_out: u64) -> Result<()> {
+    let liquid_staking_pool = &mut ctx.accounts.liquid_staking_pool;
+    if !liquid_staking_pool.get_status_by_bit(LiquidStakingPoolStatusBitIndex::Withdraw) {
+        return err!(ErrorCode::NotApproved);
+    }
+    let staked_amount = {
+        let data_ref = ctx.accounts.stake_escrow.try_borrow_data()?;
+        let stake_escrow_account = StakeEscrowAccount::deserialize(&data_ref)?;
+        if stake_escrow_account.0.owner != ctx.accounts.authority.key() {
+            return err!(ErrorCode::AccountDeserializeFailed);
+        }
+        stake_escrow_account.0.stake_amount
+    };
+
+    let total_backing = liquid_staking_pool.total_backing(ctx.accounts.token_vault.amount, staked_amount)?;
+    let gross_amount = math::lst_to_token(lst_amount, total_backing, liquid_staking_pool.liquid_supply)?;
+    let exit_fee = math::fee_amount(gross_amount, liquid_staking_pool.instant_unstake_fee_rate)?;
+    let amount_out = gross_amount.checked_sub(exit_fee).ok_or(ErrorCode::MathUnderflow)?;
+    if amount_out < min_amount_out {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+    let buffer = liquid_staking_pool.instant_unstake_buffer(ctx.accounts.token_vault.amount);
+    if amount_out > buffer {
+        return err!(ErrorCode::InsufficientBuffer);
+    }
+
+    liquid_staking_pool.liquid_supply = liquid_staking_pool.liquid_supply.checked_sub(lst_amount).ok_or(ErrorCode::InsufficientLiquidStakingToken)?;
+    let signer_seeds: &[&[&[u8]]] = &[&[crate::AUTH_SEED.as_bytes(), &[liquid_staking_pool.auth_bump]]];
+    token::token_burn(
+        ctx.accounts.staker.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.liquid_token_mint.to_account_info(),
+        ctx.accounts.staker_liquid_token_vault.to_account_info(),
+        lst_amount,
+        signer_seeds,
+    )?;
+    token::transfer_from_pool_vault_to_user(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_vault.to_account_info(),
+        ctx.accounts.staker_token_vault.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        amount_out,
+        liquid_staking_pool.token_mint_decimals,
+        signer_seeds,
+    )?;
+
+    if let Some(staker_position) = &mut ctx.accounts.staker_position {
+        staker_position.record_lst_burned(lst_amount)?;
+        staker_position.record_withdraw(amount_out)?;
+    }
+    let post_total_backing = total_backing.checked_sub(amount_out).ok_or(ErrorCode::MathUnderflow)?;
+    if let Some(rate_history) = &mut ctx.accounts.rate_history {
+        rate_history.record(&Clock::get()?, post_total_backing, liquid_staking_pool.liquid_supply);
+    }
+
+    emit_cpi!(InstantUnstaked {
+        pool: liquid_staking_pool.key(),
+        staker: ctx.accounts.staker.key(),
+        lst_amount,
+        token_amount: amount_out,
+        exit_fee,
+        buffer: buffer - amount_out,
+        liquid_supply: liquid_staking_pool.liquid_supply,
+    });
+    Ok(())
+}
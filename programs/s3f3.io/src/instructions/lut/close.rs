@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use solana_address_lookup_table_program::{
+    instruction::close_lookup_table,
+    ID as ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+};
+use solana_program::program::invoke_signed;
+use crate::AUTH_SEED;
+use crate::states::pool::LiquidStakingPool;
+use crate::error::ErrorCode;
+use crate::events::LookupTableClosed;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseLUT<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = pool.pool_creator == signer.key(),
+    )]
+    pub pool: Account<'info, LiquidStakingPool>,
+    /// CHECK: authority
+    #[account(
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = pool.retired_lut != Pubkey::default() @ ErrorCode::InvalidLookupTable,
+        constraint = lookup_table.key() == pool.retired_lut @ ErrorCode::InvalidLookupTable,
+    )]
+    /// CHECK: the account will be validated by the lookup table program
+    pub lookup_table: AccountInfo<'info>,
+
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
+    /// CHECK: the account will be validated by the lookup table program
+    pub address_lookup_table_program: AccountInfo<'info>,
+}
+
+
+/// Closes the deactivated retired lookup table and returns its rent to the signer
+pub fn close_lut(ctx: Context<CloseLUT>) -> Result<()> {
+    let ix = close_lookup_table(
+        ctx.accounts.lookup_table.key(),
+        ctx.accounts.authority.key(),
+        ctx.accounts.signer.key(),
+    );
+
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.lookup_table.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.signer.to_account_info(),
+        ],
+        &[&[crate::AUTH_SEED.as_bytes(), &[ctx.accounts.pool.auth_bump]]]
+    )?;
+    ctx.accounts.pool.retired_lut = Pubkey::default();
+
+    emit_cpi!(LookupTableClosed {
+        pool: ctx.accounts.pool.key(),
+        lookup_table: ctx.accounts.lookup_table.key(),
+        recipient: ctx.accounts.signer.key(),
+    });
+    Ok(())
+}
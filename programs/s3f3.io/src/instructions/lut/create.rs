@@ -7,14 +7,17 @@ use solana_program::program::invoke_signed;
 use crate::AUTH_SEED;
 use crate::states::pool::LiquidStakingPool;
 use crate::error::ErrorCode;
+use crate::events::LookupTableCreated;
 
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateLUT<'info> {
     pub signer: Signer<'info>,
     #[account(
         mut,
         constraint = pool.pool_creator == signer.key(),
+        constraint = pool.pending_lut == Pubkey::default() @ ErrorCode::LookupTableInUse,
     )]
     pub pool: Account<'info, LiquidStakingPool>,
     /// CHECK: authority
@@ -40,7 +43,7 @@ pub fn create_lookup_table(ctx: Context<CreateLUT>) -> Result<()> {
     let (ix, lut_key) = create_lookup_table_signed(
         ctx.accounts.authority.key(), 
         ctx.accounts.signer.key(), 
-        Clock::get()?.slot - 1
+        Clock::get()?.slot.checked_sub(1).ok_or(ErrorCode::MathUnderflow)?
     );
     msg!("lut_key {:?}", lut_key);
     msg!("lookup_table {:?}", ctx.accounts.lookup_table.key());
@@ -59,6 +62,19 @@ pub fn create_lookup_table(ctx: Context<CreateLUT>) -> Result<()> {
         },
         false => return err!(ErrorCode::CreateLookupTableFailed)
     };
-    ctx.accounts.pool.lut = lut_key;
+    // The first table goes live right away, later ones are staged for `replace_lookup_table`
+    let pool = &mut ctx.accounts.pool;
+    let pending = pool.lut != Pubkey::default();
+    if pending {
+        pool.pending_lut = lut_key;
+    } else {
+        pool.lut = lut_key;
+    }
+
+    emit_cpi!(LookupTableCreated {
+        pool: ctx.accounts.pool.key(),
+        lookup_table: lut_key,
+        pending,
+    });
     Ok(())
 }
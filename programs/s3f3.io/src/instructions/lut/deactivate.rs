@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use solana_address_lookup_table_program::{
+    instruction::deactivate_lookup_table,
+    ID as ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+};
+use solana_program::program::invoke_signed;
+use crate::AUTH_SEED;
+use crate::states::pool::LiquidStakingPool;
+use crate::error::ErrorCode;
+use crate::events::LookupTableDeactivated;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DeactivateLUT<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        constraint = pool.pool_creator == signer.key(),
+    )]
+    pub pool: Account<'info, LiquidStakingPool>,
+    /// CHECK: authority
+    #[account(
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = pool.retired_lut != Pubkey::default() @ ErrorCode::InvalidLookupTable,
+        constraint = lookup_table.key() == pool.retired_lut @ ErrorCode::InvalidLookupTable,
+    )]
+    /// CHECK: the account will be validated by the lookup table program
+    pub lookup_table: AccountInfo<'info>,
+
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
+    /// CHECK: the account will be validated by the lookup table program
+    pub address_lookup_table_program: AccountInfo<'info>,
+}
+
+
+/// Starts the cooldown after which the retired lookup table can be closed
+pub fn deactivate_lut(ctx: Context<DeactivateLUT>) -> Result<()> {
+    let ix = deactivate_lookup_table(
+        ctx.accounts.lookup_table.key(),
+        ctx.accounts.authority.key(),
+    );
+
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.lookup_table.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+        ],
+        &[&[crate::AUTH_SEED.as_bytes(), &[ctx.accounts.pool.auth_bump]]]
+    )?;
+
+    emit_cpi!(LookupTableDeactivated {
+        pool: ctx.accounts.pool.key(),
+        lookup_table: ctx.accounts.lookup_table.key(),
+    });
+    Ok(())
+}
@@ -1,13 +1,20 @@
 use anchor_lang::prelude::*;
 use solana_address_lookup_table_program::{
     instruction::extend_lookup_table,
+    state::AddressLookupTable,
     ID as ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
 };
 use solana_program::program::invoke_signed;
 use crate::AUTH_SEED;
 use crate::states::pool::LiquidStakingPool;
+use crate::error::ErrorCode;
+use crate::events::LookupTableExtended;
 
+/// Maximum number of addresses appended in a single `extend_lut` call
+pub const MAX_EXTEND_ADDRESSES: usize = 20;
 
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ExtendLUT<'info> {
     pub signer: Signer<'info>,
@@ -24,14 +31,13 @@ pub struct ExtendLUT<'info> {
         bump = pool.auth_bump,
     )]
     pub authority: UncheckedAccount<'info>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = lookup_table.key() == pool.lut || lookup_table.key() == pool.pending_lut @ ErrorCode::InvalidLookupTable,
+    )]
     /// CHECK: the account will be validated by the lookup table program
     pub lookup_table: AccountInfo<'info>,
 
-    /// CHECK: the account will be validated by the lookup table program
-    pub new_address: AccountInfo<'info>,
-
-
     #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
     /// CHECK: the account will be validated by the lookup table program
     pub address_lookup_table_program: AccountInfo<'info>,
@@ -39,14 +45,30 @@ pub struct ExtendLUT<'info> {
 }
 
 
+/// Appends the keys of `remaining_accounts` to the pool lookup table, in order
 pub fn extend_lut(ctx: Context<ExtendLUT>) -> Result<()> {
-    let new_addresses: Vec<Pubkey> = vec![ctx.accounts.new_address.key()];
+    let new_addresses: Vec<Pubkey> = ctx.remaining_accounts.iter().map(|account| account.key()).collect();
+    if new_addresses.is_empty() {
+        return err!(ErrorCode::InvalidNumberOfAccounts);
+    }
+    if new_addresses.len() > MAX_EXTEND_ADDRESSES {
+        return err!(ErrorCode::TooManyLookupTableAddresses);
+    }
+    {
+        let alt_bytes = ctx.accounts.lookup_table.try_borrow_data()?;
+        let lookup_table = AddressLookupTable::deserialize(&alt_bytes).map_err(|_| ErrorCode::InvalidLookupTableData)?;
+        for (i, address) in new_addresses.iter().enumerate() {
+            if new_addresses[..i].contains(address) || lookup_table.addresses.contains(address) {
+                return err!(ErrorCode::DuplicateLookupTableAddress);
+            }
+        }
+    }
 
     let ix = extend_lookup_table(
         ctx.accounts.lookup_table.key(), 
         ctx.accounts.authority.key(), 
         Some(ctx.accounts.signer.key()),
-        new_addresses
+        new_addresses.clone()
     );
 
     invoke_signed(
@@ -59,5 +81,11 @@ pub fn extend_lut(ctx: Context<ExtendLUT>) -> Result<()> {
         ],
         &[&[crate::AUTH_SEED.as_bytes(), &[ctx.accounts.pool.auth_bump]]]
     )?;
+
+    emit_cpi!(LookupTableExtended {
+        pool: ctx.accounts.pool.key(),
+        lookup_table: ctx.accounts.lookup_table.key(),
+        new_addresses,
+    });
     Ok(())
 }
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use solana_address_lookup_table_program::{
+    instruction::freeze_lookup_table,
+    state::AddressLookupTable,
+    ID as ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+};
+use solana_program::program::invoke_signed;
+use crate::AUTH_SEED;
+use crate::states::pool::LiquidStakingPool;
+use crate::error::ErrorCode;
+use crate::events::LookupTableFrozen;
+use crate::utils::lookup_table::METEORA_LOOKUP_TABLE_LEN;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FreezeLUT<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        constraint = pool.pool_creator == signer.key(),
+    )]
+    pub pool: Account<'info, LiquidStakingPool>,
+    /// CHECK: authority
+    #[account(
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = lookup_table.key() == pool.lut || lookup_table.key() == pool.pending_lut @ ErrorCode::InvalidLookupTable,
+    )]
+    /// CHECK: the account will be validated by the lookup table program
+    pub lookup_table: AccountInfo<'info>,
+
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
+    /// CHECK: the account will be validated by the lookup table program
+    pub address_lookup_table_program: AccountInfo<'info>,
+}
+
+
+/// Makes the lookup table immutable. A frozen table can no longer be deactivated or closed
+pub fn freeze_lut(ctx: Context<FreezeLUT>) -> Result<()> {
+    {
+        let alt_bytes = ctx.accounts.lookup_table.try_borrow_data()?;
+        let lookup_table = AddressLookupTable::deserialize(&alt_bytes).map_err(|_| ErrorCode::InvalidLookupTableData)?;
+        if lookup_table.addresses.len() < METEORA_LOOKUP_TABLE_LEN {
+            return err!(ErrorCode::LookupTableTooShort);
+        }
+    }
+
+    let ix = freeze_lookup_table(
+        ctx.accounts.lookup_table.key(),
+        ctx.accounts.authority.key(),
+    );
+
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.lookup_table.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+        ],
+        &[&[crate::AUTH_SEED.as_bytes(), &[ctx.accounts.pool.auth_bump]]]
+    )?;
+
+    emit_cpi!(LookupTableFrozen {
+        pool: ctx.accounts.pool.key(),
+        lookup_table: ctx.accounts.lookup_table.key(),
+    });
+    Ok(())
+}
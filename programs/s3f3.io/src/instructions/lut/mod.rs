@@ -2,4 +2,19 @@ pub mod create;
 pub use create::*;
 
 pub mod extend;
-pub use extend::*;
\ No newline at end of file
+pub use extend::*;
+
+pub mod freeze;
+pub use freeze::*;
+
+pub mod replace;
+pub use replace::*;
+
+pub mod deactivate;
+pub use deactivate::*;
+
+pub mod close;
+pub use close::*;
+
+pub mod populate;
+pub use populate::*;
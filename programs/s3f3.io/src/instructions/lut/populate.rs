@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use solana_address_lookup_table_program::{
+    instruction::{create_lookup_table_signed, extend_lookup_table},
+    ID as ADDRESS_LOOKUP_TABLE_PROGRAM_ID,
+};
+use solana_program::program::invoke_signed;
+use stake_for_fee_interface::id as stake_for_fee_id;
+use crate::AUTH_SEED;
+use crate::states::pool::LiquidStakingPool;
+use crate::error::ErrorCode;
+use crate::events::{LookupTableCreated, LookupTableExtended};
+use crate::utils::meteora::{
+    self,
+    AMM_POOL_A_VAULT_LP_OFFSET,
+    AMM_POOL_A_VAULT_OFFSET,
+    AMM_POOL_B_VAULT_LP_OFFSET,
+    AMM_POOL_B_VAULT_OFFSET,
+    AMM_PROGRAM_ID,
+    VAULT_LP_MINT_OFFSET,
+    VAULT_PROGRAM_ID,
+    VAULT_TOKEN_VAULT_OFFSET,
+};
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PopulateLUT<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = pool.pool_creator == signer.key(),
+        constraint = pool.fee_pool != Pubkey::default() @ ErrorCode::InvalidMeteoraAccount,
+        constraint = pool.pending_lut == Pubkey::default() @ ErrorCode::LookupTableInUse,
+    )]
+    pub pool: Account<'info, LiquidStakingPool>,
+    /// CHECK: authority
+    #[account(
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: Meteora AMM pool, read below
+    #[account(
+        constraint = fee_pool.key() == pool.fee_pool @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub fee_pool: UncheckedAccount<'info>,
+    /// CHECK: Meteora vault, checked against the AMM pool
+    pub a_vault: UncheckedAccount<'info>,
+    /// CHECK: Meteora vault, checked against the AMM pool
+    pub b_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: the account will be validated by the lookup table program
+    pub lookup_table: AccountInfo<'info>,
+
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM_ID)]
+    /// CHECK: the account will be validated by the lookup table program
+    pub address_lookup_table_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+
+/// Creates a lookup table holding the Meteora accounts of the pool in the order the
+/// CPI handlers expect, and makes it live or stages it like `create_lookup_table`
+pub fn populate_lut(ctx: Context<PopulateLUT>) -> Result<()> {
+    let fee_pool = &ctx.accounts.fee_pool.to_account_info();
+    let a_vault = meteora::read_pubkey(fee_pool, &AMM_PROGRAM_ID, AMM_POOL_A_VAULT_OFFSET)?;
+    let b_vault = meteora::read_pubkey(fee_pool, &AMM_PROGRAM_ID, AMM_POOL_B_VAULT_OFFSET)?;
+    if a_vault != ctx.accounts.a_vault.key() || b_vault != ctx.accounts.b_vault.key() {
+        return err!(ErrorCode::InvalidMeteoraAccount);
+    }
+    let a_vault_info = &ctx.accounts.a_vault.to_account_info();
+    let b_vault_info = &ctx.accounts.b_vault.to_account_info();
+
+    let new_addresses: Vec<Pubkey> = vec![
+        a_vault,
+        b_vault,
+        meteora::read_pubkey(fee_pool, &AMM_PROGRAM_ID, AMM_POOL_A_VAULT_LP_OFFSET)?,
+        meteora::read_pubkey(fee_pool, &AMM_PROGRAM_ID, AMM_POOL_B_VAULT_LP_OFFSET)?,
+        meteora::read_pubkey(a_vault_info, &VAULT_PROGRAM_ID, VAULT_LP_MINT_OFFSET)?,
+        meteora::read_pubkey(b_vault_info, &VAULT_PROGRAM_ID, VAULT_LP_MINT_OFFSET)?,
+        meteora::read_pubkey(a_vault_info, &VAULT_PROGRAM_ID, VAULT_TOKEN_VAULT_OFFSET)?,
+        meteora::read_pubkey(b_vault_info, &VAULT_PROGRAM_ID, VAULT_TOKEN_VAULT_OFFSET)?,
+        AMM_PROGRAM_ID,
+        VAULT_PROGRAM_ID,
+        meteora::event_authority(&stake_for_fee_id()),
+    ];
+
+    let signer_seeds: &[&[&[u8]]] = &[&[crate::AUTH_SEED.as_bytes(), &[ctx.accounts.pool.auth_bump]]];
+    let (ix, lut_key) = create_lookup_table_signed(
+        ctx.accounts.authority.key(),
+        ctx.accounts.signer.key(),
+        Clock::get()?.slot.checked_sub(1).ok_or(ErrorCode::MathUnderflow)?
+    );
+    if lut_key != ctx.accounts.lookup_table.key() {
+        return err!(ErrorCode::CreateLookupTableFailed);
+    }
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.lookup_table.to_account_info(),
+            ctx.accounts.signer.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        signer_seeds
+    )?;
+
+    let ix = extend_lookup_table(
+        lut_key,
+        ctx.accounts.authority.key(),
+        Some(ctx.accounts.signer.key()),
+        new_addresses.clone()
+    );
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.lookup_table.to_account_info(),
+            ctx.accounts.signer.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        signer_seeds
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    let pending = pool.lut != Pubkey::default();
+    if pending {
+        pool.pending_lut = lut_key;
+    } else {
+        pool.lut = lut_key;
+    }
+
+    emit_cpi!(LookupTableCreated {
+        pool: ctx.accounts.pool.key(),
+        lookup_table: lut_key,
+        pending,
+    });
+    emit_cpi!(LookupTableExtended {
+        pool: ctx.accounts.pool.key(),
+        lookup_table: lut_key,
+        new_addresses,
+    });
+    Ok(())
+}
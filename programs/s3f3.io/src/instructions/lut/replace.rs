@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use solana_address_lookup_table_program::state::AddressLookupTable;
+use crate::states::pool::LiquidStakingPool;
+use crate::error::ErrorCode;
+use crate::events::LookupTableReplaced;
+use crate::utils::lookup_table::METEORA_LOOKUP_TABLE_LEN;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReplaceLUT<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = pool.pool_creator == signer.key(),
+        constraint = pool.retired_lut == Pubkey::default() @ ErrorCode::LookupTableInUse,
+    )]
+    pub pool: Account<'info, LiquidStakingPool>,
+    #[account(
+        constraint = pool.pending_lut != Pubkey::default() @ ErrorCode::InvalidLookupTable,
+        constraint = new_lookup_table.key() == pool.pending_lut @ ErrorCode::InvalidLookupTable,
+    )]
+    /// CHECK: deserialized as an address lookup table below
+    pub new_lookup_table: AccountInfo<'info>,
+    #[account(
+        constraint = old_lookup_table.key() == pool.lut @ ErrorCode::InvalidLookupTable,
+    )]
+    /// CHECK: deserialized as an address lookup table below
+    pub old_lookup_table: AccountInfo<'info>,
+}
+
+
+/// Swaps the pool over to its staged lookup table and retires the current one.
+/// A frozen table can never be deactivated or closed, so it is dropped instead of retired
+pub fn replace_lut(ctx: Context<ReplaceLUT>) -> Result<()> {
+    {
+        let alt_bytes = ctx.accounts.new_lookup_table.try_borrow_data()?;
+        let lookup_table = AddressLookupTable::deserialize(&alt_bytes).map_err(|_| ErrorCode::InvalidLookupTableData)?;
+        if lookup_table.addresses.len() < METEORA_LOOKUP_TABLE_LEN {
+            return err!(ErrorCode::LookupTableTooShort);
+        }
+        if lookup_table.meta.deactivation_slot != u64::MAX {
+            return err!(ErrorCode::InvalidLookupTable);
+        }
+    }
+    let retired = if ctx.accounts.pool.lut == Pubkey::default() {
+        false
+    } else {
+        let alt_bytes = ctx.accounts.old_lookup_table.try_borrow_data()?;
+        let lookup_table = AddressLookupTable::deserialize(&alt_bytes).map_err(|_| ErrorCode::InvalidLookupTableData)?;
+        lookup_table.meta.authority.is_some()
+    };
+
+    let pool = &mut ctx.accounts.pool;
+    let old_lookup_table = pool.lut;
+    if retired {
+        pool.retired_lut = old_lookup_table;
+    }
+    pool.lut = pool.pending_lut;
+    pool.pending_lut = Pubkey::default();
+
+    emit_cpi!(LookupTableReplaced {
+        pool: pool.key(),
+        old_lookup_table,
+        new_lookup_table: pool.lut,
+        retired,
+    });
+    Ok(())
+}
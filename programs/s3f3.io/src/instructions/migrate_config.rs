@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::states::config::{Config, CONFIG_SEED, DEFAULT_MAX_DEPOSIT_FEE_RATE, DEFAULT_MAX_REWARD_FEE_RATE};
+use crate::events::ConfigMigrated;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: a pre-migration config is too short to deserialize as `Config`
+    #[account(
+        mut,
+        seeds = [
+            CONFIG_SEED.as_bytes(),
+        ],
+        bump,
+        owner = crate::ID,
+    )]
+    pub config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a config created before the fee caps and guardian fields to the current layout.
+/// The old padding, now `pending_authority`, was never written, so it and the new fields start zeroed.
+pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    let old_len = config_info.data_len();
+    if old_len >= Config::LEN {
+        return err!(ErrorCode::ConfigAlreadyMigrated);
+    }
+    if old_len != Config::LEGACY_LEN {
+        return err!(ErrorCode::AccountDeserializeFailed);
+    }
+
+    let lamports = Rent::get()?.minimum_balance(Config::LEN).saturating_sub(config_info.lamports());
+    if lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: config_info.clone(),
+                },
+            ),
+            lamports,
+        )?;
+    }
+    config_info.realloc(Config::LEN, true)?;
+
+    let mut config = Config::try_deserialize(&mut &config_info.try_borrow_data()?[..])?;
+    if config.creator_authority != ctx.accounts.signer.key() {
+        return err!(ErrorCode::NotApproved);
+    }
+    config.max_deposit_fee_rate = DEFAULT_MAX_DEPOSIT_FEE_RATE;
+    config.max_reward_fee_rate = DEFAULT_MAX_REWARD_FEE_RATE;
+    config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+    emit_cpi!(ConfigMigrated {
+        config: config_info.key(),
+        authority: config.creator_authority,
+        old_len: old_len as u64,
+        new_len: Config::LEN as u64,
+    });
+    Ok(())
+}
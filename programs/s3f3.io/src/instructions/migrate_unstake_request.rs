@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{system_program, Discriminator};
+use stake_for_fee_interface::accounts::UnstakeAccount;
+use crate::states::config::Config;
+use crate::states::pool::LiquidStakingPool;
+use crate::states::unstake_request::{LegacyLstUnstakeRequest, LstUnstakeRequest};
+use crate::events::UnstakeRequestMigrated;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateUnstakeRequest<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub staker: Signer<'info>,
+    pub liquid_staking_pool: Box<Account<'info, LiquidStakingPool>>,
+    /// CHECK: a legacy request is too short to deserialize as `LstUnstakeRequest`
+    #[account(
+        mut,
+        owner = crate::ID,
+    )]
+    pub lst_unstake_request: UncheckedAccount<'info>,
+    /// CHECK: Meteora Unstake, deserialized below
+    pub unstake: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a request created at a keypair address before requests were derived from
+/// (pool, owner, nonce) to the current layout, so `withdraw` and `cancel_unstake` accept it.
+/// The pool is bound through the Meteora unstake's escrow.
+pub fn migrate_unstake_request(ctx: Context<MigrateUnstakeRequest>) -> Result<()> {
+    let request_info = ctx.accounts.lst_unstake_request.to_account_info();
+    let legacy = {
+        let data = request_info.try_borrow_data()?;
+        if data.len() != LegacyLstUnstakeRequest::LEN || data[..8] != LstUnstakeRequest::DISCRIMINATOR {
+            return err!(ErrorCode::AccountDeserializeFailed);
+        }
+        LegacyLstUnstakeRequest::deserialize(&mut &data[8..])?
+    };
+    if legacy.owner != ctx.accounts.staker.key() {
+        return err!(ErrorCode::InvalidUnstakeRequestOwner);
+    }
+    if legacy.unstake != ctx.accounts.unstake.key() {
+        return err!(ErrorCode::InvalidUnstakeRequestUnstake);
+    }
+    let token_amount = {
+        let data_ref = ctx.accounts.unstake.try_borrow_data()?;
+        let unstake_account = UnstakeAccount::deserialize(&data_ref)?;
+        if unstake_account.0.stake_escrow != ctx.accounts.liquid_staking_pool.escrow {
+            return err!(ErrorCode::InvalidMeteoraAccount);
+        }
+        unstake_account.0.unstake_amount
+    };
+
+    let lamports = Rent::get()?.minimum_balance(LstUnstakeRequest::LEN).saturating_sub(request_info.lamports());
+    if lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.staker.to_account_info(),
+                    to: request_info.clone(),
+                },
+            ),
+            lamports,
+        )?;
+    }
+    request_info.realloc(LstUnstakeRequest::LEN, true)?;
+
+    let request = LstUnstakeRequest {
+        owner: legacy.owner,
+        unstake: legacy.unstake,
+        pool: ctx.accounts.liquid_staking_pool.key(),
+        nonce: 0,
+        lst_amount: 0,
+        token_amount,
+        exchange_rate: 0,
+        created_slot: 0,
+        created_at: 0,
+        bump: 0,
+        ticket_mint: Pubkey::default(),
+        padding: [0; 12],
+    };
+    request.try_serialize(&mut &mut request_info.try_borrow_mut_data()?[..])?;
+
+    emit_cpi!(UnstakeRequestMigrated {
+        pool: ctx.accounts.liquid_staking_pool.key(),
+        owner: legacy.owner,
+        lst_unstake_request: request_info.key(),
+        unstake: legacy.unstake,
+        token_amount,
+    });
+    Ok(())
+}
@@ -1,6 +1,9 @@
 pub mod initialize_escrow;
 pub use initialize_escrow::*;
 
+pub mod backfill_meteora_accounts;
+pub use backfill_meteora_accounts::*;
+
 pub mod stake; 
 pub use stake::*;
 
@@ -10,6 +13,12 @@ pub use unstake::*;
 pub mod initialize_config;
 pub use initialize_config::*;
 
+pub mod migrate_config;
+pub use migrate_config::*;
+
+pub mod set_fee_rate_caps;
+pub use set_fee_rate_caps::*;
+
 pub mod initialize_pool;
 pub use initialize_pool::*;
 
@@ -19,8 +28,68 @@ pub use withdraw::*;
 pub mod cancel_unstake;
 pub use cancel_unstake::*;
 
+pub mod migrate_unstake_request;
+pub use migrate_unstake_request::*;
+
 pub mod claim_fees;
 pub use claim_fees::*;
 
+pub mod update_pool_fees;
+pub use update_pool_fees::*;
+
+pub mod withdraw_protocol_fees;
+pub use withdraw_protocol_fees::*;
+
+pub mod initialize_staker_position;
+pub use initialize_staker_position::*;
+
+pub mod initialize_rate_history;
+pub use initialize_rate_history::*;
+
+pub mod get_exchange_rate;
+pub use get_exchange_rate::*;
+
+pub mod set_pool_status;
+pub use set_pool_status::*;
+
+pub mod set_guardian;
+pub use set_guardian::*;
+
+pub mod set_global_pause;
+pub use set_global_pause::*;
+
+pub mod config_authority;
+pub use config_authority::*;
+
 pub mod lut;
 pub use lut::*;
+
+pub mod set_compound_route;
+pub use set_compound_route::*;
+
+pub mod compound;
+pub use compound::*;
+
+pub mod initialize_quote_reward_checkpoint;
+pub use initialize_quote_reward_checkpoint::*;
+
+pub mod set_quote_reward_mode;
+pub use set_quote_reward_mode::*;
+
+pub mod settle_quote_rewards;
+pub use settle_quote_rewards::*;
+
+pub mod claim_quote_rewards;
+pub use claim_quote_rewards::*;
+
+pub mod set_instant_unstake_params;
+pub use set_instant_unstake_params::*;
+
+pub mod instant_unstake;
+pub use instant_unstake::*;
+
+pub mod buffer;
+pub use buffer::*;
+
+pub mod unstake_queue;
+pub use unstake_queue::*;
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::states::config::Config;
+use crate::states::pool::LiquidStakingPool;
+use crate::events::CompoundRouteUpdated;
+use crate::error::ErrorCode;
+use crate::utils::math::BPS_DENOMINATOR;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCompoundRoute<'info> {
+    #[account(
+        constraint = config.creator_authority == signer.key() @ ErrorCode::NotApproved,
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub pool: Account<'info, LiquidStakingPool>,
+}
+
+/// The swap program is invoked with the pool authority as signer, so only the config authority may set it.
+/// Setting `Pubkey::default()` disables compounding. `max_slippage_bps` bounds how far below the
+/// AMM reserve quote a compound swap may fill, and must be non-zero for compounding to run.
+pub fn set_compound_route(ctx: Context<SetCompoundRoute>, swap_program: Pubkey, swap_pool: Pubkey, max_slippage_bps: u64) -> Result<()> {
+    if max_slippage_bps > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidCompoundSlippage);
+    }
+    let pool = &mut ctx.accounts.pool;
+    pool.swap_program = swap_program;
+    pool.swap_pool = swap_pool;
+    pool.max_compound_slippage_bps = max_slippage_bps;
+
+    emit_cpi!(CompoundRouteUpdated {
+        pool: pool.key(),
+        swap_program,
+        swap_pool,
+        max_slippage_bps,
+    });
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::states::config::{Config, FEE_RATE_DENOMINATOR};
+use crate::events::FeeRateCapsUpdated;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetFeeRateCaps<'info> {
+    #[account(
+        mut,
+        constraint = config.creator_authority == signer.key() @ ErrorCode::NotApproved,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+}
+
+/// Lowering a cap does not touch pools already above it, it only bounds later `update_pool_fees`.
+pub fn set_fee_rate_caps(ctx: Context<SetFeeRateCaps>, max_deposit_fee_rate: u64, max_reward_fee_rate: u64) -> Result<()> {
+    if max_deposit_fee_rate > FEE_RATE_DENOMINATOR || max_reward_fee_rate > FEE_RATE_DENOMINATOR {
+        return err!(ErrorCode::FeeRateTooHigh);
+    }
+    let config = &mut ctx.accounts.config;
+    let old_max_deposit_fee_rate = config.max_deposit_fee_rate;
+    let old_max_reward_fee_rate = config.max_reward_fee_rate;
+    config.max_deposit_fee_rate = max_deposit_fee_rate;
+    config.max_reward_fee_rate = max_reward_fee_rate;
+
+    emit_cpi!(FeeRateCapsUpdated {
+        config: config.key(),
+        old_max_deposit_fee_rate,
+        new_max_deposit_fee_rate: max_deposit_fee_rate,
+        old_max_reward_fee_rate,
+        new_max_reward_fee_rate: max_reward_fee_rate,
+    });
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::states::config::Config;
+use crate::events::GlobalPauseUpdated;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetGlobalPause<'info> {
+    #[account(
+        mut,
+        constraint = config.creator_authority == signer.key() || config.is_guardian(&signer.key()) @ ErrorCode::NotApproved,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+}
+
+pub fn set_global_pause(ctx: Context<SetGlobalPause>, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    if !paused && config.creator_authority != ctx.accounts.signer.key() {
+        return err!(ErrorCode::GuardianCannotEnable);
+    }
+    config.paused = paused;
+
+    emit_cpi!(GlobalPauseUpdated {
+        config: config.key(),
+        authority: ctx.accounts.signer.key(),
+        paused,
+    });
+    Ok(())
+}
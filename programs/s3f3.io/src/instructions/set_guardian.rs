@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::states::config::Config;
+use crate::events::GuardianUpdated;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        constraint = config.creator_authority == signer.key() @ ErrorCode::NotApproved,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+}
+
+/// Setting `Pubkey::default()` removes the guardian.
+pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_guardian = config.guardian;
+    config.guardian = guardian;
+
+    emit_cpi!(GuardianUpdated {
+        config: config.key(),
+        old_guardian,
+        new_guardian: guardian,
+    });
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::states::config::{Config, MAX_INSTANT_UNSTAKE_FEE_RATE};
+use crate::states::pool::LiquidStakingPool;
+use crate::events::InstantUnstakeParamsUpdated;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetInstantUnstakeParams<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = config.creator_authority == signer.key() || pool.pool_creator == signer.key() @ ErrorCode::NotApproved,
+    )]
+    pub pool: Account<'info, LiquidStakingPool>,
+}
+
+/// A zero `buffer_target` stops the replenish crank, instant unstakes still drain what is left
+pub fn set_instant_unstake_params(ctx: Context<SetInstantUnstakeParams>, instant_unstake_fee_rate: u64, buffer_target: u64) -> Result<()> {
+    if instant_unstake_fee_rate > MAX_INSTANT_UNSTAKE_FEE_RATE {
+        return err!(ErrorCode::FeeRateTooHigh);
+    }
+    let pool = &mut ctx.accounts.pool;
+    pool.instant_unstake_fee_rate = instant_unstake_fee_rate;
+    pool.buffer_target = buffer_target;
+
+    emit_cpi!(InstantUnstakeParamsUpdated {
+        pool: pool.key(),
+        authority: ctx.accounts.signer.key(),
+        instant_unstake_fee_rate,
+        buffer_target,
+    });
+    Ok(())
+}
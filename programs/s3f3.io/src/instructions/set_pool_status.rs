@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::states::config::Config;
+use crate::states::pool::{LiquidStakingPool, LIQUID_STAKING_POOL_STATUS_MASK};
+use crate::events::PoolStatusUpdated;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPoolStatus<'info> {
+    #[account(
+        constraint = config.creator_authority == signer.key() || config.is_guardian(&signer.key()) @ ErrorCode::NotApproved,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub pool: Account<'info, LiquidStakingPool>,
+}
+
+pub fn set_pool_status(ctx: Context<SetPoolStatus>, status: u8) -> Result<()> {
+    if status & !LIQUID_STAKING_POOL_STATUS_MASK != 0 {
+        return err!(ErrorCode::InvalidInput);
+    }
+    let pool = &mut ctx.accounts.pool;
+    let old_status = pool.status;
+    // The guardian may only disable operations, re-enabling is left to the authority
+    if ctx.accounts.config.creator_authority != ctx.accounts.signer.key() && status & old_status != old_status {
+        return err!(ErrorCode::GuardianCannotEnable);
+    }
+    pool.set_status(status);
+
+    emit_cpi!(PoolStatusUpdated {
+        pool: pool.key(),
+        authority: ctx.accounts.signer.key(),
+        old_status,
+        new_status: status,
+    });
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::states::config::Config;
+use crate::states::pool::LiquidStakingPool;
+use crate::events::QuoteRewardModeUpdated;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetQuoteRewardMode<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = config.creator_authority == signer.key() || pool.pool_creator == signer.key() @ ErrorCode::NotApproved,
+    )]
+    pub pool: Account<'info, LiquidStakingPool>,
+    #[account(
+        constraint = quote_vault.key() == pool.quote_vault_account @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// Quote received before the switch is allocated under the old mode: disabling first pays it to
+/// checkpoints, enabling keeps the balance back for `compound`
+pub fn set_quote_reward_mode(ctx: Context<SetQuoteRewardMode>, distribute_quote_rewards: bool) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.set_distribute_quote_rewards(distribute_quote_rewards, ctx.accounts.quote_vault.amount)?;
+
+    emit_cpi!(QuoteRewardModeUpdated {
+        pool: pool.key(),
+        authority: ctx.accounts.signer.key(),
+        distribute_quote_rewards,
+    });
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::states::config::Config;
+use crate::states::pool::LiquidStakingPool;
+use crate::states::quote_reward_checkpoint::{QuoteRewardCheckpoint, QUOTE_REWARD_CHECKPOINT_SEED};
+use crate::events::QuoteRewardsSettled;
+use crate::error::ErrorCode;
+use crate::utils::token;
+use crate::AUTH_SEED;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleQuoteRewards<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub pool: Box<Account<'info, LiquidStakingPool>>,
+    /// CHECK: authority
+    #[account(
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            QUOTE_REWARD_CHECKPOINT_SEED.as_bytes(),
+            pool.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump = checkpoint.bump,
+    )]
+    pub checkpoint: Box<Account<'info, QuoteRewardCheckpoint>>,
+    #[account(
+        constraint = quote_vault.key() == pool.quote_vault_account @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        constraint = liquid_token_mint.key() == pool.liquid_token_mint @ ErrorCode::InvalidLiquidStakingTokenMint,
+    )]
+    pub liquid_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// LST custody of the checkpoints
+    #[account(
+        mut,
+        constraint = liquid_token_vault.key() == pool.liquid_token_vault @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub liquid_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = owner_liquid_token_vault.mint == pool.liquid_token_mint @ ErrorCode::InvalidLiquidStakingTokenMint,
+        constraint = owner_liquid_token_vault.owner == owner.key() @ ErrorCode::InvalidLiquidStakingTokenOwner,
+    )]
+    pub owner_liquid_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK:
+    pub token_program: UncheckedAccount<'info>,
+}
+
+/// Settles rewards at the current index, then moves LST into or out of checkpoint custody.
+/// Only LST in custody earns quote rewards, so a transfer can never be counted twice.
+/// A deposit locks the whole checkpoint for `MIN_CHECKPOINT_DURATION`, and starts earning only after the next
+/// `claim_fees`, so it can't take Meteora fees that accrued before it. Its rewards until then go to `compound`.
+pub fn settle_quote_rewards(ctx: Context<SettleQuoteRewards>, deposit_amount: u64, withdraw_amount: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.accrue_quote_rewards(ctx.accounts.quote_vault.amount)?;
+    let checkpoint = &mut ctx.accounts.checkpoint;
+    let forfeited = checkpoint.settle(pool.quote_reward_per_lst, pool.quote_fee_claims)?;
+    pool.release_quote_rewards(forfeited)?;
+
+    if deposit_amount > 0 {
+        token::transfer_from_user_to_pool_vault(
+            ctx.accounts.owner.to_account_info(),
+            ctx.accounts.owner_liquid_token_vault.to_account_info(),
+            ctx.accounts.liquid_token_vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            deposit_amount,
+        )?;
+        let forfeited = checkpoint.deposit(deposit_amount, pool.quote_reward_per_lst, pool.quote_fee_claims, Clock::get()?.unix_timestamp)?;
+        pool.release_quote_rewards(forfeited)?;
+        pool.checkpointed_lst = pool.checkpointed_lst.checked_add(deposit_amount).ok_or(ErrorCode::OverflowError)?;
+    }
+    if withdraw_amount > 0 {
+        let forfeited = checkpoint.withdraw(withdraw_amount, pool.quote_reward_per_lst, Clock::get()?.unix_timestamp)?;
+        pool.release_quote_rewards(forfeited)?;
+        pool.checkpointed_lst = pool.checkpointed_lst.checked_sub(withdraw_amount).ok_or(ErrorCode::InsufficientLiquidStakingToken)?;
+        token::transfer_from_pool_vault_to_user(
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.liquid_token_vault.to_account_info(),
+            ctx.accounts.owner_liquid_token_vault.to_account_info(),
+            ctx.accounts.liquid_token_mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            withdraw_amount,
+            ctx.accounts.liquid_token_mint.decimals,
+            &[&[crate::AUTH_SEED.as_bytes(), &[pool.auth_bump]]],
+        )?;
+    }
+
+    emit_cpi!(QuoteRewardsSettled {
+        pool: pool.key(),
+        owner: ctx.accounts.owner.key(),
+        deposit_amount,
+        withdraw_amount,
+        lst_amount: checkpoint.lst_amount,
+        accrued_quote: checkpoint.accrued_quote,
+        quote_reward_per_lst: pool.quote_reward_per_lst,
+    });
+    Ok(())
+}
@@ -10,19 +10,30 @@ use stake_for_fee_interface::{
 };
 use anchor_spl::token_interface::{Mint, TokenAccount};
 use solana_program::{instruction::Instruction, program::invoke_signed};
-use solana_address_lookup_table_program::state::AddressLookupTable;
 use crate::{
+    states::config::Config,
     states::pool::{LiquidStakingPool, LiquidStakingPoolStatusBitIndex},
+    states::rate_history::{RateHistory, RATE_HISTORY_SEED},
+    states::staker_position::{StakerPosition, STAKER_POSITION_SEED},
     error::ErrorCode,
-    utils::token,
+    events::Staked,
+    utils::{lookup_table, math, token},
     AUTH_SEED,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct StakeCpi<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
     #[account(mut)]
     pub staker: Signer<'info>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = !liquid_staking_pool.rate_history_initialized || rate_history.is_some() @ ErrorCode::RateHistoryRequired,
+    )]
     pub liquid_staking_pool: Account<'info, LiquidStakingPool>,
     /// CHECK
     #[account(
@@ -61,38 +72,87 @@ pub struct StakeCpi<'info> {
     /// CHECK:
     pub cpi_program: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vault.key() == liquid_staking_pool.vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub vault: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = stake_token_vault.key() == liquid_staking_pool.stake_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub stake_token_vault: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = quote_token_vault.key() == liquid_staking_pool.quote_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub quote_token_vault: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = top_staker_list.key() == liquid_staking_pool.top_staker_list @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub top_staker_list: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = full_balance_list.key() == liquid_staking_pool.full_balance_list @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub full_balance_list: UncheckedAccount<'info>,
     /// CHECK: StakeEscrow is handled by CPI
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = stake_escrow.key() == liquid_staking_pool.escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub stake_escrow: UncheckedAccount<'info>,
     /// CHECK:
     #[account(mut)]
     pub smallest_stake_escrow: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = fee_pool.key() == liquid_staking_pool.fee_pool @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub fee_pool: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = lp_mint.key() == liquid_staking_pool.lp_mint @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = lock_escrow.key() == liquid_staking_pool.lock_escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub lock_escrow: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = escrow_vault.key() == liquid_staking_pool.escrow_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub escrow_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            RATE_HISTORY_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+        ],
+        bump = rate_history.bump,
+    )]
+    pub rate_history: Option<Box<Account<'info, RateHistory>>>,
+    #[account(
+        mut,
+        seeds = [
+            STAKER_POSITION_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+            staker.key().as_ref(),
+        ],
+        bump = staker_position.bump,
+    )]
+    pub staker_position: Option<Box<Account<'info, StakerPosition>>>,
     /// CHECK:
     pub token_program: UncheckedAccount<'info>,
     /// CHECK:
@@ -127,7 +187,8 @@ pub fn stake_ix_with_program_id(
 
 pub fn stake_cpi<'info>(
     ctx: Context<'_, '_, '_, 'info, StakeCpi<'info>>,
-    amount: u64
+    amount: u64,
+    min_liquid_out: u64,
 ) -> Result<()> {
     msg!("========= S3F3.io =========");
     let liquid_staking_pool = &mut ctx.accounts.liquid_staking_pool;
@@ -148,15 +209,13 @@ pub fn stake_cpi<'info>(
             return err!(ErrorCode::AccountDeserializeFailed);
         }
     }
-    let deposit_fee = amount.checked_mul(liquid_staking_pool.deposit_fee_rate).unwrap().checked_div(100).unwrap();
+    let deposit_fee = math::fee_amount(amount, liquid_staking_pool.deposit_fee_rate)?;
 
-    if liquid_staking_pool.lut != ctx.accounts.lookup_table.key() {
-        return err!(ErrorCode::InvalidLookupTable);
-    }
-    let alt_bytes = ctx.accounts.lookup_table.try_borrow_data()?;
-    let lookup_table: AddressLookupTable = AddressLookupTable::deserialize(&alt_bytes).unwrap();
-    let lookup_table_len = lookup_table.addresses.len();
-    let address_lookup_table_account_infos: &[AccountInfo] = ctx.remaining_accounts.get(..lookup_table_len).ok_or(ErrorCode::InvalidLookupTable)?;
+    let (address_lookup_table_account_infos, leftover_infos) = lookup_table::split_lookup_table_accounts(
+        &ctx.accounts.lookup_table,
+        &liquid_staking_pool.lut,
+        ctx.remaining_accounts,
+    )?;
     
     token::transfer_from_user_to_pool_vault(
         ctx.accounts.staker.to_account_info(),
@@ -202,7 +261,6 @@ pub fn stake_cpi<'info>(
         amount: amount,
     };
     let keys: StakeKeys = accounts_cpi.into();
-    let leftover_infos = &ctx.remaining_accounts[lookup_table_len..];
     if leftover_infos.len() > 3 {
         return err!(ErrorCode::InvalidNumberOfAccounts);
     }
@@ -215,32 +273,23 @@ pub fn stake_cpi<'info>(
 
     invoke_signed(&ix, &all_infos, &[&[crate::AUTH_SEED.as_bytes(), &[liquid_staking_pool.auth_bump]]])?;
 
-    let amount_after_fee = amount.checked_sub(deposit_fee).unwrap();
-    let liquid_supply = liquid_staking_pool.liquid_supply;
-    let mut liquid_amount_out = 0;
-    if liquid_supply != 0 {
-        let staked_u128 = (ctx.accounts.token_vault.amount as u128)
-            .checked_add(lst_pool_staked_amount as u128).unwrap()
-            .checked_sub(liquid_staking_pool.protocol_fees_token as u128)
-            .ok_or(ErrorCode::OverflowError)?; 
+    let amount_after_fee = amount.checked_sub(deposit_fee).ok_or(ErrorCode::MathUnderflow)?;
+    let total_backing = liquid_staking_pool.total_backing(ctx.accounts.token_vault.amount, lst_pool_staked_amount)?;
+    let liquid_amount_out = math::token_to_lst(amount_after_fee, total_backing, liquid_staking_pool.liquid_supply)?;
+    if liquid_amount_out < min_liquid_out {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+    liquid_staking_pool.liquid_supply = liquid_staking_pool.liquid_supply.checked_add(liquid_amount_out).ok_or(ErrorCode::OverflowError)?;
 
-        let scaled_u128 = (liquid_supply as u128)
-            .checked_mul(amount_after_fee as u128)
-            .ok_or(ErrorCode::OverflowError)?;
-        let liquid_amount_out_u128 = scaled_u128
-            .checked_div(staked_u128)
-            .ok_or(ErrorCode::DivisionError)?;
+    liquid_staking_pool.protocol_fees_token = liquid_staking_pool.protocol_fees_token.checked_add(deposit_fee).ok_or(ErrorCode::OverflowError)?;
 
-        if liquid_amount_out_u128 > u64::MAX as u128 {
-            return err!(ErrorCode::OverflowError);
-        }
-        liquid_amount_out = liquid_amount_out_u128 as u64;
-    } else {
-        liquid_amount_out = amount_after_fee;
+    if let Some(staker_position) = &mut ctx.accounts.staker_position {
+        staker_position.record_deposit(amount, deposit_fee, liquid_amount_out)?;
+    }
+    let post_total_backing = total_backing.checked_add(amount_after_fee).ok_or(ErrorCode::OverflowError)?;
+    if let Some(rate_history) = &mut ctx.accounts.rate_history {
+        rate_history.record(&Clock::get()?, post_total_backing, liquid_staking_pool.liquid_supply);
     }
-    liquid_staking_pool.liquid_supply = liquid_staking_pool.liquid_supply.checked_add(liquid_amount_out).unwrap();
-
-    liquid_staking_pool.protocol_fees_token = liquid_staking_pool.protocol_fees_token.checked_add(deposit_fee).unwrap();
 
     token::token_mint_to(
         ctx.accounts.authority.to_account_info(),
@@ -250,5 +299,15 @@ pub fn stake_cpi<'info>(
         liquid_amount_out,
         &[&[crate::AUTH_SEED.as_bytes(), &[liquid_staking_pool.auth_bump]]],
     )?;
+
+    emit_cpi!(Staked {
+        pool: liquid_staking_pool.key(),
+        staker: ctx.accounts.staker.key(),
+        amount,
+        deposit_fee,
+        liquid_amount_out,
+        total_backing: post_total_backing,
+        liquid_supply: liquid_staking_pool.liquid_supply,
+    });
     Ok(())
 }
\ No newline at end of file
@@ -7,22 +7,37 @@ use stake_for_fee_interface::{
     instructions::{RequestUnstakeAccounts, RequestUnstakeIxArgs, request_unstake_invoke_signed},
     id as stake_for_fee_id,
 };
-use anchor_spl::token_interface::{Mint, TokenAccount};
-use solana_address_lookup_table_program::state::AddressLookupTable;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{self, mpl_token_metadata::types::DataV2, Metadata},
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
 use solana_program::{instruction::Instruction, program::invoke_signed};
 
 use crate::{
     states::{
+        config::Config,
         pool::{LiquidStakingPool, LiquidStakingPoolStatusBitIndex},
-        unstake_request::LstUnstakeRequest,
+        staker_position::{StakerPosition, STAKER_POSITION_SEED},
+        unstake_request::{
+            LstUnstakeRequest, UnstakeRequestCounter, LST_UNSTAKE_REQUEST_SEED, UNSTAKE_REQUEST_COUNTER_SEED,
+            UNSTAKE_TICKET_MINT_SEED, UNSTAKE_TICKET_NAME, UNSTAKE_TICKET_SYMBOL,
+        },
     },
     error::ErrorCode,
-    utils::token,
+    events::UnstakeRequested,
+    utils::{lookup_table, math, token},
     AUTH_SEED,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RequestUnstakeCpi<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
     #[account(mut)]
     pub staker: Signer<'info>,
     #[account(mut)]
@@ -53,12 +68,31 @@ pub struct RequestUnstakeCpi<'info> {
         constraint = staker_liquid_token_vault.owner == staker.key() @ ErrorCode::InvalidLiquidStakingTokenOwner,
     )]
     pub staker_liquid_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = staker,
+        space = UnstakeRequestCounter::LEN,
+        seeds = [
+            UNSTAKE_REQUEST_COUNTER_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+            staker.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub unstake_request_counter: Box<Account<'info, UnstakeRequestCounter>>,
     #[account(
         init,
         payer = staker,
         space = LstUnstakeRequest::LEN,
+        seeds = [
+            LST_UNSTAKE_REQUEST_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+            staker.key().as_ref(),
+            &unstake_request_counter.next_nonce.to_le_bytes(),
+        ],
+        bump,
     )]
-    pub lst_unstake_request: Account<'info, LstUnstakeRequest>,
+    pub lst_unstake_request: Box<Account<'info, LstUnstakeRequest>>,
     /// CHECK: the account will be validated by the lookup table program
     pub lookup_table: AccountInfo<'info>,
     /// CHECK:
@@ -67,35 +101,126 @@ pub struct RequestUnstakeCpi<'info> {
     /// CHECK:
     pub cpi_program: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vault.key() == liquid_staking_pool.vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub vault: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = stake_token_vault.key() == liquid_staking_pool.stake_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub stake_token_vault: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = quote_token_vault.key() == liquid_staking_pool.quote_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub quote_token_vault: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = top_staker_list.key() == liquid_staking_pool.top_staker_list @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub top_staker_list: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = full_balance_list.key() == liquid_staking_pool.full_balance_list @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub full_balance_list: UncheckedAccount<'info>,
     /// CHECK: StakeEscrow is handled by CPI
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = stake_escrow.key() == liquid_staking_pool.escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub stake_escrow: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = fee_pool.key() == liquid_staking_pool.fee_pool @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub fee_pool: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = lp_mint.key() == liquid_staking_pool.lp_mint @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = lock_escrow.key() == liquid_staking_pool.lock_escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub lock_escrow: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = escrow_vault.key() == liquid_staking_pool.escrow_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub escrow_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            STAKER_POSITION_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+            staker.key().as_ref(),
+        ],
+        bump = staker_position.bump,
+    )]
+    pub staker_position: Option<Box<Account<'info, StakerPosition>>>,
+    #[account(
+        init,
+        payer = staker,
+        seeds = [
+            UNSTAKE_TICKET_MINT_SEED.as_bytes(),
+            lst_unstake_request.key().as_ref(),
+        ],
+        bump,
+        mint::decimals = 0,
+        mint::authority = authority,
+        mint::freeze_authority = authority,
+        mint::token_program = ticket_token_program,
+    )]
+    pub ticket_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = staker,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = staker,
+        associated_token::token_program = ticket_token_program,
+    )]
+    pub staker_ticket_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: created by the token metadata program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            ticket_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = metadata_program.key(),
+    )]
+    pub ticket_metadata: UncheckedAccount<'info>,
+    /// CHECK: created by the token metadata program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            ticket_mint.key().as_ref(),
+            b"edition",
+        ],
+        bump,
+        seeds::program = metadata_program.key(),
+    )]
+    pub ticket_edition: UncheckedAccount<'info>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub ticket_token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
     /// CHECK:
     pub token_program: UncheckedAccount<'info>,
     /// CHECK:
@@ -129,7 +254,7 @@ pub fn request_unstake_ix_with_program_id(
 }
 
 
-pub fn request_unstake_cpi<'info>( ctx: Context<'_, '_, '_, 'info, RequestUnstakeCpi<'info>>, lst_unstake_amount: u64) -> Result<()> {
+pub fn request_unstake_cpi<'info>( ctx: Context<'_, '_, '_, 'info, RequestUnstakeCpi<'info>>, lst_unstake_amount: u64, min_amount_out: u64) -> Result<()> {
     let liquid_staking_pool = &mut ctx.accounts.liquid_staking_pool;
     if !liquid_staking_pool.get_status_by_bit(LiquidStakingPoolStatusBitIndex::Withdraw) {
         return err!(ErrorCode::NotApproved);
@@ -148,24 +273,19 @@ pub fn request_unstake_cpi<'info>( ctx: Context<'_, '_, '_, 'info, RequestUnstak
         }
     }
 
-    // staked_full / amount = liq_sup / unstake_amount
-    // staked_full * unstake_amount = liq_sup * amount
-    // amount = staked_full * liquid_amount_out_u128 / liq_sup
-    // Multiply in 128-bit space
-
-    let amount_out: u64 = (ctx.accounts.token_vault.amount as u128 + lst_pool_staked_amount as u128 - liquid_staking_pool.protocol_fees_token as u128)
-        .checked_mul(lst_unstake_amount as u128).unwrap()
-        .checked_div(liquid_staking_pool.liquid_supply as u128).ok_or(ErrorCode::OverflowError)? as u64;
-
-    liquid_staking_pool.liquid_supply = liquid_staking_pool.liquid_supply.checked_sub(lst_unstake_amount).unwrap();
-    if liquid_staking_pool.lut != ctx.accounts.lookup_table.key() {
-        return err!(ErrorCode::InvalidLookupTable);
+    let total_backing = liquid_staking_pool.total_backing(ctx.accounts.token_vault.amount, lst_pool_staked_amount)?;
+    let amount_out = math::lst_to_token(lst_unstake_amount, total_backing, liquid_staking_pool.liquid_supply)?;
+    if amount_out < min_amount_out {
+        return err!(ErrorCode::SlippageExceeded);
     }
+    let exchange_rate = math::exchange_rate(total_backing, liquid_staking_pool.liquid_supply)?;
 
-    let alt_bytes = ctx.accounts.lookup_table.try_borrow_data()?;
-    let lookup_table: AddressLookupTable = AddressLookupTable::deserialize(&alt_bytes).unwrap();
-    let lookup_table_len = lookup_table.addresses.len();
-    let address_lookup_table_account_infos: &[AccountInfo] = ctx.remaining_accounts.get(..lookup_table_len).ok_or(ErrorCode::InvalidLookupTable)?;
+    liquid_staking_pool.liquid_supply = liquid_staking_pool.liquid_supply.checked_sub(lst_unstake_amount).ok_or(ErrorCode::InsufficientLiquidStakingToken)?;
+    let (address_lookup_table_account_infos, leftover_infos) = lookup_table::split_lookup_table_accounts(
+        &ctx.accounts.lookup_table,
+        &liquid_staking_pool.lut,
+        ctx.remaining_accounts,
+    )?;
     let rent = Rent::get()?;
     let lamports = rent.minimum_balance(8 + std::mem::size_of::<UnstakeAccount>());
     let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
@@ -174,9 +294,30 @@ pub fn request_unstake_cpi<'info>( ctx: Context<'_, '_, '_, 'info, RequestUnstak
         lamports
     );
 
+    let counter = &mut ctx.accounts.unstake_request_counter;
+    counter.pool = liquid_staking_pool.key();
+    counter.owner = ctx.accounts.staker.key();
+    counter.bump = ctx.bumps.unstake_request_counter;
+    let nonce = counter.next_nonce;
+    counter.next_nonce = nonce.checked_add(1).ok_or(ErrorCode::OverflowError)?;
+
+    if let Some(staker_position) = &mut ctx.accounts.staker_position {
+        staker_position.record_lst_burned(lst_unstake_amount)?;
+    }
+
+    let clock = Clock::get()?;
     let unstake_request = &mut ctx.accounts.lst_unstake_request;
     unstake_request.owner = ctx.accounts.staker.key();
     unstake_request.unstake = ctx.accounts.unstake.key();
+    unstake_request.pool = liquid_staking_pool.key();
+    unstake_request.nonce = nonce;
+    unstake_request.lst_amount = lst_unstake_amount;
+    unstake_request.token_amount = amount_out;
+    unstake_request.exchange_rate = exchange_rate;
+    unstake_request.created_slot = clock.slot;
+    unstake_request.created_at = clock.unix_timestamp;
+    unstake_request.bump = ctx.bumps.lst_unstake_request;
+    unstake_request.ticket_mint = ctx.accounts.ticket_mint.key();
 
     anchor_lang::solana_program::program::invoke(
         &transfer_ix,
@@ -223,7 +364,6 @@ pub fn request_unstake_cpi<'info>( ctx: Context<'_, '_, '_, 'info, RequestUnstak
     };
 
 
-    let leftover_infos = &ctx.remaining_accounts[lookup_table_len..];
     if leftover_infos.len() > 3 {
         return err!(ErrorCode::InvalidNumberOfAccounts);
     }
@@ -246,5 +386,75 @@ pub fn request_unstake_cpi<'info>( ctx: Context<'_, '_, '_, 'info, RequestUnstak
         &[&[crate::AUTH_SEED.as_bytes(), &[liquid_staking_pool.auth_bump]]],
     )?;
 
-    Ok(())
-}
\ No newline at end of file
+    emit_cpi!(UnstakeRequested {
+        pool: liquid_staking_pool.key(),
+        staker: ctx.accounts.staker.key(),
+        lst_unstake_request: ctx.accounts.lst_unstake_request.key(),
+        unstake: ctx.accounts.unstake.key(),
+        ticket_mint: ctx.accounts.ticket_mint.key(),
+        lst_amount: lst_unstake_amount,
+        token_amount: amount_out,
+        exchange_rate,
+        liquid_supply: liquid_staking_pool.liquid_supply,
+    });
+    mint_unstake_ticket(&ctx, nonce)
+}
+/// Mints the 1-of-1 ticket that authorizes withdrawing or cancelling the request,
+/// so the pending exit can change hands before the cooldown ends
+fn mint_unstake_ticket<'info>(ctx: &Context<'_, '_, '_, 'info, RequestUnstakeCpi<'info>>, nonce: u64) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[crate::AUTH_SEED.as_bytes(), &[ctx.accounts.liquid_staking_pool.auth_bump]]];
+    token::token_mint_to(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.ticket_token_program.to_account_info(),
+        ctx.accounts.ticket_mint.to_account_info(),
+        ctx.accounts.staker_ticket_account.to_account_info(),
+        1,
+        signer_seeds,
+    )?;
+    metadata::create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.metadata_program.to_account_info(),
+            metadata::CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.ticket_metadata.to_account_info(),
+                mint: ctx.accounts.ticket_mint.to_account_info(),
+                mint_authority: ctx.accounts.authority.to_account_info(),
+                payer: ctx.accounts.staker.to_account_info(),
+                update_authority: ctx.accounts.authority.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        DataV2 {
+            name: format!("{} #{}", UNSTAKE_TICKET_NAME, nonce),
+            symbol: UNSTAKE_TICKET_SYMBOL.to_string(),
+            uri: String::new(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        false,
+        true,
+        None,
+    )?;
+    // Zero max supply moves the mint authority to the edition, fixing the supply at one
+    metadata::create_master_edition_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.metadata_program.to_account_info(),
+            metadata::CreateMasterEditionV3 {
+                edition: ctx.accounts.ticket_edition.to_account_info(),
+                mint: ctx.accounts.ticket_mint.to_account_info(),
+                update_authority: ctx.accounts.authority.to_account_info(),
+                mint_authority: ctx.accounts.authority.to_account_info(),
+                payer: ctx.accounts.staker.to_account_info(),
+                metadata: ctx.accounts.ticket_metadata.to_account_info(),
+                token_program: ctx.accounts.ticket_token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        Some(0),
+    )
+}
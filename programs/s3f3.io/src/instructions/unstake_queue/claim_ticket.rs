@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::{
+    states::{
+        config::Config,
+        pool::LiquidStakingPool,
+        staker_position::{StakerPosition, STAKER_POSITION_SEED},
+        unstake_queue::{UnstakeBucket, UnstakeBucketStatus, UnstakeTicket},
+    },
+    error::ErrorCode,
+    events::UnstakeTicketClaimed,
+    utils::{math, token},
+    AUTH_SEED,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimUnstakeTicket<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub staker: Signer<'info>,
+    #[account(mut)]
+    pub liquid_staking_pool: Box<Account<'info, LiquidStakingPool>>,
+    /// CHECK
+    #[account(
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = liquid_staking_pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = bucket.pool == liquid_staking_pool.key() @ ErrorCode::NotApproved,
+        constraint = bucket.status == UnstakeBucketStatus::Withdrawn @ ErrorCode::InvalidUnstakeBucketStatus,
+    )]
+    pub bucket: Box<Account<'info, UnstakeBucket>>,
+    #[account(
+        mut,
+        close = staker,
+        has_one = bucket,
+        constraint = ticket.owner == staker.key() @ ErrorCode::NotApproved,
+    )]
+    pub ticket: Box<Account<'info, UnstakeTicket>>,
+    #[account(
+        mut,
+        constraint = token_vault.key() == liquid_staking_pool.token_vault_account @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        constraint = token_mint.key() == liquid_staking_pool.token_mint @ ErrorCode::InvalidInput,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = staker_token_vault.owner == staker.key() @ ErrorCode::InvalidLiquidStakingTokenOwner,
+        constraint = staker_token_vault.mint == liquid_staking_pool.token_mint @ ErrorCode::InvalidLiquidStakingTokenMint,
+    )]
+    pub staker_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [
+            STAKER_POSITION_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+            staker.key().as_ref(),
+        ],
+        bump = staker_position.bump,
+    )]
+    pub staker_position: Option<Box<Account<'info, StakerPosition>>>,
+    /// CHECK:
+    pub token_program: UncheckedAccount<'info>,
+}
+
+/// Pays out the ticket's pro rata share of what its bucket withdrew and closes the ticket
+pub fn claim_unstake_ticket(ctx: Context<ClaimUnstakeTicket>) -> Result<()> {
+    let liquid_staking_pool = &mut ctx.accounts.liquid_staking_pool;
+    let bucket = &mut ctx.accounts.bucket;
+    let ticket = &ctx.accounts.ticket;
+    let amount = math::pro_rata(ticket.token_amount, bucket.withdrawn_amount, bucket.token_amount)?;
+    // Rounding dust of a fully claimed bucket returns to the backing
+    let dust = bucket.record_claim(ticket.token_amount, amount)?;
+    liquid_staking_pool.claimable_unstake_amount = liquid_staking_pool.claimable_unstake_amount
+        .checked_sub(amount)
+        .and_then(|claimable| claimable.checked_sub(dust))
+        .ok_or(ErrorCode::MathUnderflow)?;
+
+    token::transfer_from_pool_vault_to_user(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_vault.to_account_info(),
+        ctx.accounts.staker_token_vault.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        amount,
+        liquid_staking_pool.token_mint_decimals,
+        &[&[crate::AUTH_SEED.as_bytes(), &[liquid_staking_pool.auth_bump]]],
+    )?;
+    if let Some(staker_position) = &mut ctx.accounts.staker_position {
+        staker_position.record_withdraw(amount)?;
+    }
+
+    emit_cpi!(UnstakeTicketClaimed {
+        pool: liquid_staking_pool.key(),
+        staker: ctx.accounts.staker.key(),
+        bucket: bucket.key(),
+        bucket_id: bucket.id,
+        lst_amount: ticket.lst_amount,
+        amount,
+    });
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use stake_for_fee_interface::accounts::StakeEscrowAccount;
+use crate::{
+    states::{
+        config::Config,
+        pool::{LiquidStakingPool, LiquidStakingPoolStatusBitIndex},
+        staker_position::{StakerPosition, STAKER_POSITION_SEED},
+        unstake_queue::{UnstakeBucket, UnstakeBucketStatus, UnstakeTicket, UNSTAKE_BUCKET_SEED, UNSTAKE_TICKET_SEED},
+    },
+    error::ErrorCode,
+    events::UnstakeEnqueued,
+    utils::{math, token},
+    AUTH_SEED,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EnqueueUnstake<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub staker: Signer<'info>,
+    #[account(mut)]
+    pub liquid_staking_pool: Box<Account<'info, LiquidStakingPool>>,
+    /// CHECK
+    #[account(
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = liquid_staking_pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        constraint = token_vault.key() == liquid_staking_pool.token_vault_account @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = liquid_token_mint.key() == liquid_staking_pool.liquid_token_mint @ ErrorCode::InvalidLiquidStakingTokenMint,
+    )]
+    pub liquid_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = staker_liquid_token_vault.mint == liquid_staking_pool.liquid_token_mint @ ErrorCode::InvalidLiquidStakingTokenMint,
+        constraint = staker_liquid_token_vault.owner == staker.key() @ ErrorCode::InvalidLiquidStakingTokenOwner,
+    )]
+    pub staker_liquid_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: StakeEscrow, read for the staked amount
+    #[account(
+        constraint = stake_escrow.key() == liquid_staking_pool.escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub stake_escrow: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = staker,
+        space = UnstakeBucket::LEN,
+        seeds = [
+            UNSTAKE_BUCKET_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+            &liquid_staking_pool.recent_epoch.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub bucket: Box<Account<'info, UnstakeBucket>>,
+    #[account(
+        init_if_needed,
+        payer = staker,
+        space = UnstakeTicket::LEN,
+        seeds = [
+            UNSTAKE_TICKET_SEED.as_bytes(),
+            bucket.key().as_ref(),
+            staker.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub ticket: Box<Account<'info, UnstakeTicket>>,
+    #[account(
+        mut,
+        seeds = [
+            STAKER_POSITION_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+            staker.key().as_ref(),
+        ],
+        bump = staker_position.bump,
+    )]
+    pub staker_position: Option<Box<Account<'info, StakerPosition>>>,
+    /// CHECK:
+    pub token_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Burns LST at the current rate into the open unstake bucket, to be unstaked from
+/// Meteora together with the rest of the bucket
+pub fn enqueue_unstake(ctx: Context<EnqueueUnstake>, lst_amount: u64, min_amount_out: u64) -> Result<()> {
+    let liquid_staking_pool = &mut ctx.accounts.liquid_staking_pool;
+    if !liquid_staking_pool.get_status_by_bit(LiquidStakingPoolStatusBitIndex::Withdraw) {
+        return err!(ErrorCode::NotApproved);
+    }
+    let staked_amount = {
+        let data_ref = ctx.accounts.stake_escrow.try_borrow_data()?;
+        let stake_escrow_account = StakeEscrowAccount::deserialize(&data_ref)?;
+        if stake_escrow_account.0.owner != ctx.accounts.authority.key() {
+            return err!(ErrorCode::AccountDeserializeFailed);
+        }
+        stake_escrow_account.0.stake_amount
+    };
+
+    let total_backing = liquid_staking_pool.total_backing(ctx.accounts.token_vault.amount, staked_amount)?;
+    let amount_out = math::lst_to_token(lst_amount, total_backing, liquid_staking_pool.liquid_supply)?;
+    if lst_amount == 0 || amount_out == 0 {
+        return err!(ErrorCode::ZeroUnstakeAmount);
+    }
+    if amount_out < min_amount_out {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+    liquid_staking_pool.liquid_supply = liquid_staking_pool.liquid_supply.checked_sub(lst_amount).ok_or(ErrorCode::InsufficientLiquidStakingToken)?;
+    liquid_staking_pool.queued_unstake_amount = liquid_staking_pool.queued_unstake_amount.checked_add(amount_out).ok_or(ErrorCode::OverflowError)?;
+
+    let bucket = &mut ctx.accounts.bucket;
+    if bucket.pool == Pubkey::default() {
+        bucket.pool = liquid_staking_pool.key();
+        bucket.id = liquid_staking_pool.recent_epoch;
+        bucket.opened_epoch = Clock::get()?.epoch;
+        bucket.status = UnstakeBucketStatus::Open;
+        bucket.bump = ctx.bumps.bucket;
+    }
+    bucket.lst_amount = bucket.lst_amount.checked_add(lst_amount).ok_or(ErrorCode::OverflowError)?;
+    bucket.token_amount = bucket.token_amount.checked_add(amount_out).ok_or(ErrorCode::OverflowError)?;
+
+    let ticket = &mut ctx.accounts.ticket;
+    if ticket.owner == Pubkey::default() {
+        ticket.pool = liquid_staking_pool.key();
+        ticket.bucket = bucket.key();
+        ticket.owner = ctx.accounts.staker.key();
+        ticket.bump = ctx.bumps.ticket;
+    }
+    ticket.lst_amount = ticket.lst_amount.checked_add(lst_amount).ok_or(ErrorCode::OverflowError)?;
+    ticket.token_amount = ticket.token_amount.checked_add(amount_out).ok_or(ErrorCode::OverflowError)?;
+
+    if let Some(staker_position) = &mut ctx.accounts.staker_position {
+        staker_position.record_lst_burned(lst_amount)?;
+    }
+
+    token::token_burn(
+        ctx.accounts.staker.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.liquid_token_mint.to_account_info(),
+        ctx.accounts.staker_liquid_token_vault.to_account_info(),
+        lst_amount,
+        &[&[crate::AUTH_SEED.as_bytes(), &[liquid_staking_pool.auth_bump]]],
+    )?;
+
+    emit_cpi!(UnstakeEnqueued {
+        pool: liquid_staking_pool.key(),
+        staker: ctx.accounts.staker.key(),
+        bucket: bucket.key(),
+        bucket_id: bucket.id,
+        lst_amount,
+        token_amount: amount_out,
+        liquid_supply: liquid_staking_pool.liquid_supply,
+    });
+    Ok(())
+}
@@ -0,0 +1,11 @@
+pub mod enqueue;
+pub use enqueue::*;
+
+pub mod request_bucket;
+pub use request_bucket::*;
+
+pub mod withdraw_bucket;
+pub use withdraw_bucket::*;
+
+pub mod claim_ticket;
+pub use claim_ticket::*;
@@ -0,0 +1,223 @@
+use anchor_lang::prelude::*;
+use stake_for_fee_interface::{
+    RequestUnstakeKeys,
+    REQUEST_UNSTAKE_IX_ACCOUNTS_LEN,
+    accounts::UnstakeAccount,
+    instructions::{RequestUnstakeAccounts, RequestUnstakeIxArgs},
+    id as stake_for_fee_id,
+};
+use anchor_spl::token_interface::Mint;
+use solana_program::program::invoke_signed;
+
+use crate::{
+    instructions::unstake::request_unstake_ix_with_program_id,
+    states::{
+        config::Config,
+        pool::LiquidStakingPool,
+        unstake_queue::{UnstakeBucket, UnstakeBucketStatus, UNSTAKE_BUCKET_SEED},
+    },
+    error::ErrorCode,
+    events::UnstakeBucketRequested,
+    utils::lookup_table,
+    AUTH_SEED,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RequestUnstakeBucket<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(mut)]
+    pub liquid_staking_pool: Box<Account<'info, LiquidStakingPool>>,
+    /// CHECK
+    #[account(
+        mut,
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = liquid_staking_pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            UNSTAKE_BUCKET_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+            &liquid_staking_pool.recent_epoch.to_le_bytes(),
+        ],
+        bump = bucket.bump,
+        constraint = bucket.status == UnstakeBucketStatus::Open @ ErrorCode::InvalidUnstakeBucketStatus,
+    )]
+    pub bucket: Box<Account<'info, UnstakeBucket>>,
+    /// CHECK: the account will be validated by the lookup table program
+    pub lookup_table: AccountInfo<'info>,
+    /// CHECK:
+    #[account(mut)]
+    pub unstake: Signer<'info>,
+    /// CHECK:
+    pub cpi_program: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = vault.key() == liquid_staking_pool.vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = stake_token_vault.key() == liquid_staking_pool.stake_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub stake_token_vault: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = quote_token_vault.key() == liquid_staking_pool.quote_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub quote_token_vault: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = top_staker_list.key() == liquid_staking_pool.top_staker_list @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub top_staker_list: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = full_balance_list.key() == liquid_staking_pool.full_balance_list @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub full_balance_list: UncheckedAccount<'info>,
+    /// CHECK: StakeEscrow is handled by CPI
+    #[account(
+        mut,
+        constraint = stake_escrow.key() == liquid_staking_pool.escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub stake_escrow: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = fee_pool.key() == liquid_staking_pool.fee_pool @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub fee_pool: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = lp_mint.key() == liquid_staking_pool.lp_mint @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = lock_escrow.key() == liquid_staking_pool.lock_escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub lock_escrow: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        mut,
+        constraint = escrow_vault.key() == liquid_staking_pool.escrow_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
+    pub escrow_vault: UncheckedAccount<'info>,
+    /// CHECK:
+    pub token_program: UncheckedAccount<'info>,
+    /// CHECK:
+    pub system_program: UncheckedAccount<'info>,
+}
+
+/// Permissionless crank: closes the open bucket once its epoch is over and requests a
+/// single Meteora unstake for everything enqueued into it
+pub fn request_unstake_bucket<'info>(ctx: Context<'_, '_, '_, 'info, RequestUnstakeBucket<'info>>) -> Result<()> {
+    let liquid_staking_pool = &mut ctx.accounts.liquid_staking_pool;
+    let bucket = &mut ctx.accounts.bucket;
+    if Clock::get()?.epoch <= bucket.opened_epoch {
+        return err!(ErrorCode::UnstakeBucketEpochNotEnded);
+    }
+    let amount = bucket.token_amount;
+    if amount == 0 {
+        return err!(ErrorCode::EmptyUnstakeBucket);
+    }
+
+    let (address_lookup_table_account_infos, leftover_infos) = lookup_table::split_lookup_table_accounts(
+        &ctx.accounts.lookup_table,
+        &liquid_staking_pool.lut,
+        ctx.remaining_accounts,
+    )?;
+    if leftover_infos.len() > 3 {
+        return err!(ErrorCode::InvalidNumberOfAccounts);
+    }
+    if ctx.accounts.cpi_program.key() != stake_for_fee_id() {
+        return err!(ErrorCode::InvalidCpiProgram);
+    }
+
+    // The cranker funds the rent of the unstake account, as stakers do in `request_unstake`
+    let lamports = Rent::get()?.minimum_balance(8 + std::mem::size_of::<UnstakeAccount>());
+    anchor_lang::solana_program::program::invoke(
+        &anchor_lang::solana_program::system_instruction::transfer(
+            &ctx.accounts.cranker.key(),
+            &ctx.accounts.authority.key(),
+            lamports,
+        ),
+        &[
+            ctx.accounts.cranker.to_account_info(),
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    let accounts_cpi: RequestUnstakeAccounts<'_, '_> = RequestUnstakeAccounts {
+        unstake: &ctx.accounts.unstake.to_account_info(),
+        vault: &ctx.accounts.vault.to_account_info(),
+        stake_token_vault: &ctx.accounts.stake_token_vault.to_account_info(),
+        quote_token_vault: &ctx.accounts.quote_token_vault.to_account_info(),
+        top_staker_list: &ctx.accounts.top_staker_list.to_account_info(),
+        full_balance_list: &ctx.accounts.full_balance_list.to_account_info(),
+        stake_escrow: &ctx.accounts.stake_escrow.to_account_info(),
+        owner: &ctx.accounts.authority.to_account_info(),
+        pool: &ctx.accounts.fee_pool.to_account_info(),
+        lp_mint: &ctx.accounts.lp_mint.to_account_info(),
+        lock_escrow: &ctx.accounts.lock_escrow.to_account_info(),
+        escrow_vault: &ctx.accounts.escrow_vault.to_account_info(),
+        a_vault: &address_lookup_table_account_infos[0],
+        b_vault: &address_lookup_table_account_infos[1],
+        a_vault_lp: &address_lookup_table_account_infos[2],
+        b_vault_lp: &address_lookup_table_account_infos[3],
+        a_vault_lp_mint: &address_lookup_table_account_infos[4],
+        b_vault_lp_mint: &address_lookup_table_account_infos[5],
+        a_token_vault: &address_lookup_table_account_infos[6],
+        b_token_vault: &address_lookup_table_account_infos[7],
+        amm_program: &address_lookup_table_account_infos[8],
+        vault_program: &address_lookup_table_account_infos[9],
+        event_authority: &address_lookup_table_account_infos[10],
+        token_program: &ctx.accounts.token_program.to_account_info(),
+        program: &ctx.accounts.cpi_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    };
+    let keys: RequestUnstakeKeys = accounts_cpi.into();
+    let ix = request_unstake_ix_with_program_id(
+        ctx.accounts.cpi_program.key(),
+        keys,
+        leftover_infos,
+        RequestUnstakeIxArgs { unstake_amount: amount },
+    )?;
+    let base_infos: [AccountInfo<'info>; REQUEST_UNSTAKE_IX_ACCOUNTS_LEN] = accounts_cpi.into();
+    let mut all_infos = Vec::with_capacity(REQUEST_UNSTAKE_IX_ACCOUNTS_LEN + leftover_infos.len());
+    all_infos.extend_from_slice(&base_infos);
+    all_infos.extend_from_slice(leftover_infos);
+    invoke_signed(&ix, &all_infos, &[&[crate::AUTH_SEED.as_bytes(), &[liquid_staking_pool.auth_bump]]])?;
+
+    liquid_staking_pool.queued_unstake_amount = liquid_staking_pool.queued_unstake_amount.checked_sub(amount).ok_or(ErrorCode::MathUnderflow)?;
+    liquid_staking_pool.recent_epoch = liquid_staking_pool.recent_epoch.checked_add(1).ok_or(ErrorCode::OverflowError)?;
+    bucket.status = UnstakeBucketStatus::Requested;
+    bucket.unstake = ctx.accounts.unstake.key();
+
+    emit_cpi!(UnstakeBucketRequested {
+        pool: liquid_staking_pool.key(),
+        bucket: bucket.key(),
+        bucket_id: bucket.id,
+        unstake: ctx.accounts.unstake.key(),
+        token_amount: amount,
+    });
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::states::config::Config;
+use crate::states::pool::LiquidStakingPool;
+use crate::events::PoolFeesUpdated;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePoolFees<'info> {
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = config.creator_authority == signer.key() || pool.pool_creator == signer.key() @ ErrorCode::NotApproved,
+    )]
+    pub pool: Account<'info, LiquidStakingPool>,
+}
+
+/// Admin only and left open during a global pause, so fees can still be corrected mid-incident
+pub fn update_pool_fees(ctx: Context<UpdatePoolFees>, deposit_fee_rate: u64, reward_fee_rate: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    if deposit_fee_rate > config.max_deposit_fee_rate || reward_fee_rate > config.max_reward_fee_rate {
+        return err!(ErrorCode::FeeRateTooHigh);
+    }
+
+    let pool = &mut ctx.accounts.pool;
+    let old_deposit_fee_rate = pool.deposit_fee_rate;
+    let old_reward_fee_rate = pool.reward_fee_rate;
+    pool.deposit_fee_rate = deposit_fee_rate;
+    pool.reward_fee_rate = reward_fee_rate;
+
+    emit_cpi!(PoolFeesUpdated {
+        pool: pool.key(),
+        authority: ctx.accounts.signer.key(),
+        old_deposit_fee_rate,
+        new_deposit_fee_rate: deposit_fee_rate,
+        old_reward_fee_rate,
+        new_reward_fee_rate: reward_fee_rate,
+    });
+    Ok(())
+}
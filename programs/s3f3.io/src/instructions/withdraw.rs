@@ -1,18 +1,32 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::TokenAccount;
+use anchor_spl::{
+    metadata::Metadata,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
 use stake_for_fee_interface::{
     accounts::StakeEscrowAccount,
     instructions::withdraw_invoke_signed,
+    id as stake_for_fee_id,
     WithdrawAccounts,
 };
+use crate::states::config::Config;
 use crate::states::pool::{LiquidStakingPool, LiquidStakingPoolStatusBitIndex};
 use crate::AUTH_SEED;
 use crate::error::ErrorCode;
+use crate::events::Withdrawn;
+use crate::states::staker_position::{StakerPosition, STAKER_POSITION_SEED};
 use crate::states::unstake_request::LstUnstakeRequest;
+use crate::utils::token;
 
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct WithdrawCpi<'info> {
+    #[account(
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
     #[account(mut)]
     pub staker: Signer<'info>,
     #[account(mut)]
@@ -41,7 +55,7 @@ pub struct WithdrawCpi<'info> {
     #[account(
         mut,
         close = staker,
-        constraint = lst_unstake_request.owner == staker.key() @ ErrorCode::InvalidUnstakeRequestOwner,
+        constraint = lst_unstake_request.pool == liquid_staking_pool.key() @ ErrorCode::NotApproved,
         constraint = lst_unstake_request.unstake == unstake.key() @ ErrorCode::InvalidUnstakeRequestUnstake,
     )]
     pub lst_unstake_request: Account<'info, LstUnstakeRequest>,
@@ -51,20 +65,62 @@ pub struct WithdrawCpi<'info> {
     /// CHECK:
     pub cpi_program: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vault.key() == liquid_staking_pool.vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub vault: UncheckedAccount<'info>,
     /// CHECK:    
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = stake_token_vault.key() == liquid_staking_pool.stake_token_vault @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub stake_token_vault: UncheckedAccount<'info>,
 
     /// CHECK: StakeEscrow is handled by CPI
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = stake_escrow.key() == liquid_staking_pool.escrow @ ErrorCode::InvalidMeteoraAccount,
+    )]
     pub stake_escrow: UncheckedAccount<'info>,
 
+    /// Position of the request's original staker, who may not be the signing ticket holder
+    #[account(
+        mut,
+        seeds = [
+            STAKER_POSITION_SEED.as_bytes(),
+            liquid_staking_pool.key().as_ref(),
+            lst_unstake_request.owner.as_ref(),
+        ],
+        bump = staker_position.bump,
+    )]
+    pub staker_position: Option<Box<Account<'info, StakerPosition>>>,
+    /// Required when `lst_unstake_request` has a ticket, which the signer must hold
+    #[account(
+        mut,
+        address = lst_unstake_request.ticket_mint @ ErrorCode::InvalidUnstakeTicket,
+    )]
+    pub ticket_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(
+        mut,
+        constraint = staker_ticket_account.mint == lst_unstake_request.ticket_mint @ ErrorCode::InvalidUnstakeTicket,
+        constraint = staker_ticket_account.owner == staker.key() @ ErrorCode::InvalidUnstakeRequestOwner,
+        constraint = staker_ticket_account.amount == 1 @ ErrorCode::InvalidUnstakeRequestOwner,
+    )]
+    pub staker_ticket_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// CHECK: validated by the token metadata program on burn
+    #[account(mut)]
+    pub ticket_metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by the token metadata program on burn
+    #[account(mut)]
+    pub ticket_edition: Option<UncheckedAccount<'info>>,
+    /// Required with the ticket accounts
+    pub metadata_program: Option<Program<'info, Metadata>>,
+    pub ticket_token_program: Option<Program<'info, Token>>,
     /// CHECK:
     pub token_program: UncheckedAccount<'info>,
     /// CHECK:
-    pub event_authority: UncheckedAccount<'info>,
+    pub cpi_event_authority: UncheckedAccount<'info>,
     /// CHECK:
     pub system_program: UncheckedAccount<'info>,
 }
@@ -78,12 +134,38 @@ pub fn withdraw_cpi(ctx: Context<WithdrawCpi>) -> Result<()> {
             return err!(ErrorCode::AccountDeserializeFailed);
         }
     }
+    let ticket_accounts = match (
+        &ctx.accounts.ticket_mint,
+        &ctx.accounts.staker_ticket_account,
+        &ctx.accounts.ticket_metadata,
+        &ctx.accounts.ticket_edition,
+        &ctx.accounts.ticket_token_program,
+        &ctx.accounts.metadata_program,
+    ) {
+        (Some(mint), Some(token), Some(metadata), Some(edition), Some(token_program), Some(metadata_program)) => Some([
+            mint.to_account_info(),
+            token.to_account_info(),
+            metadata.to_account_info(),
+            edition.to_account_info(),
+            token_program.to_account_info(),
+            metadata_program.to_account_info(),
+        ]),
+        _ => None,
+    };
+    token::settle_unstake_ticket(
+        &ctx.accounts.lst_unstake_request,
+        ctx.accounts.staker.to_account_info(),
+        ticket_accounts,
+    )?;
 
     let liquid_staking_pool = &mut ctx.accounts.liquid_staking_pool;
     if !liquid_staking_pool.get_status_by_bit(LiquidStakingPoolStatusBitIndex::Withdraw) {
         return err!(ErrorCode::NotApproved);
     }
 
+    if ctx.accounts.cpi_program.key() != stake_for_fee_id() {
+        return err!(ErrorCode::InvalidCpiProgram);
+    }
     let accounts_cpi: WithdrawAccounts<'_, '_> = WithdrawAccounts {
         unstake: &ctx.accounts.unstake.to_account_info(),
         vault: &ctx.accounts.vault.to_account_info(),
@@ -92,9 +174,24 @@ pub fn withdraw_cpi(ctx: Context<WithdrawCpi>) -> Result<()> {
         stake_escrow: &ctx.accounts.stake_escrow.to_account_info(),
         owner: &ctx.accounts.authority.to_account_info(),
         token_program: &ctx.accounts.token_program.to_account_info(),
-        event_authority: &ctx.accounts.event_authority.to_account_info(),
+        event_authority: &ctx.accounts.cpi_event_authority.to_account_info(),
         program: &ctx.accounts.cpi_program.to_account_info(),
     };
+    let balance_before = ctx.accounts.staker_token_vault.amount;
     withdraw_invoke_signed(accounts_cpi, &[&[crate::AUTH_SEED.as_bytes(), &[liquid_staking_pool.auth_bump]]])?;
+
+    ctx.accounts.staker_token_vault.reload()?;
+    let withdrawn = ctx.accounts.staker_token_vault.amount.checked_sub(balance_before).ok_or(ErrorCode::MathUnderflow)?;
+    if let Some(staker_position) = &mut ctx.accounts.staker_position {
+        staker_position.record_withdraw(withdrawn)?;
+    }
+
+    emit_cpi!(Withdrawn {
+        pool: liquid_staking_pool.key(),
+        staker: ctx.accounts.staker.key(),
+        lst_unstake_request: ctx.accounts.lst_unstake_request.key(),
+        unstake: ctx.accounts.unstake.key(),
+        amount: withdrawn,
+    });
     Ok(())
 }
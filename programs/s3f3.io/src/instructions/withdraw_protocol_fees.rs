@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::states::config::Config;
+use crate::states::pool::LiquidStakingPool;
+use crate::events::ProtocolFeesWithdrawn;
+use crate::utils::token;
+use crate::AUTH_SEED;
+use crate::error::ErrorCode;
+
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    #[account(
+        constraint = config.creator_authority == signer.key() @ ErrorCode::NotApproved,
+        constraint = !config.paused @ ErrorCode::ProtocolPaused,
+    )]
+    pub config: Box<Account<'info, Config>>,
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub pool: Box<Account<'info, LiquidStakingPool>>,
+    /// CHECK: authority
+    #[account(
+        seeds = [
+            AUTH_SEED.as_bytes(),
+        ],
+        bump = pool.auth_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = token_vault.key() == pool.token_vault_account @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = quote_vault.key() == pool.quote_vault_account @ ErrorCode::InvalidLiquidStakingTokenVault,
+    )]
+    pub quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        constraint = token_mint.key() == pool.token_mint @ ErrorCode::InvalidLiquidStakingTokenMint,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        constraint = quote_mint.key() == quote_vault.mint @ ErrorCode::InvalidLiquidStakingTokenMint,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == pool.token_mint @ ErrorCode::InvalidLiquidStakingTokenMint,
+        constraint = treasury_token_account.owner == config.creator_authority @ ErrorCode::InvalidLiquidStakingTokenOwner,
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = treasury_quote_account.mint == quote_vault.mint @ ErrorCode::InvalidLiquidStakingTokenMint,
+        constraint = treasury_quote_account.owner == config.creator_authority @ ErrorCode::InvalidLiquidStakingTokenOwner,
+    )]
+    pub treasury_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Moves accrued protocol fees out of the pool vaults. Only the balance that is both
+/// accrued and sitting idle in a vault can be withdrawn, so the tokens backing the
+/// LST supply and those owed to claimable unstake tickets are never touched.
+pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, token_amount: u64, quote_amount: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let max_token_amount = pool.withdrawable_protocol_fees_token(ctx.accounts.token_vault.amount);
+    let max_quote_amount = pool.protocol_fees_quote.min(ctx.accounts.quote_vault.amount);
+    if token_amount > max_token_amount || quote_amount > max_quote_amount {
+        return err!(ErrorCode::InsufficientProtocolFees);
+    }
+
+    pool.protocol_fees_token = pool.protocol_fees_token.checked_sub(token_amount).ok_or(ErrorCode::MathUnderflow)?;
+    pool.protocol_fees_quote = pool.protocol_fees_quote.checked_sub(quote_amount).ok_or(ErrorCode::MathUnderflow)?;
+
+    token::transfer_from_pool_vault_to_user(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_vault.to_account_info(),
+        ctx.accounts.treasury_token_account.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        token_amount,
+        ctx.accounts.token_mint.decimals,
+        &[&[crate::AUTH_SEED.as_bytes(), &[pool.auth_bump]]],
+    )?;
+    token::transfer_from_pool_vault_to_user(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.quote_vault.to_account_info(),
+        ctx.accounts.treasury_quote_account.to_account_info(),
+        ctx.accounts.quote_mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        quote_amount,
+        ctx.accounts.quote_mint.decimals,
+        &[&[crate::AUTH_SEED.as_bytes(), &[pool.auth_bump]]],
+    )?;
+
+    emit_cpi!(ProtocolFeesWithdrawn {
+        pool: pool.key(),
+        token_amount,
+        quote_amount,
+        remaining_protocol_fees_token: pool.protocol_fees_token,
+        remaining_protocol_fees_quote: pool.protocol_fees_quote,
+    });
+    Ok(())
+}
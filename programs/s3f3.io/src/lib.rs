@@ -1,6 +1,7 @@
 pub mod instructions;
 pub mod states;
 pub mod error;
+pub mod events;
 pub mod utils;
 
 use anchor_lang::prelude::*;
@@ -22,21 +23,105 @@ pub mod rememe {
     pub fn initialize_escrow(_ctx: Context<InitializeEscrowCpi>) -> Result<()> {
         instructions::initialize_escrow::initialize_escrow_cpi(_ctx)
     }
-    pub fn stake<'info>(ctx: Context<'_, '_, '_, 'info, StakeCpi<'info>>, amount: u64) -> Result<()> {
-        instructions::stake::stake_cpi(ctx, amount)
+    pub fn backfill_meteora_accounts(ctx: Context<BackfillMeteoraAccounts>) -> Result<()> {
+        instructions::backfill_meteora_accounts::backfill_meteora_accounts(ctx)
     }
-    pub fn request_unstake<'info>(ctx: Context<'_, '_, '_, 'info, RequestUnstakeCpi<'info>>, amount: u64) -> Result<()> {
-        instructions::unstake::request_unstake_cpi(ctx, amount)
+    pub fn stake<'info>(ctx: Context<'_, '_, '_, 'info, StakeCpi<'info>>, amount: u64, min_liquid_out: u64) -> Result<()> {
+        instructions::stake::stake_cpi(ctx, amount, min_liquid_out)
+    }
+    pub fn request_unstake<'info>(ctx: Context<'_, '_, '_, 'info, RequestUnstakeCpi<'info>>, amount: u64, min_amount_out: u64) -> Result<()> {
+        instructions::unstake::request_unstake_cpi(ctx, amount, min_amount_out)
     }
     pub fn withdraw(_ctx: Context<WithdrawCpi>) -> Result<()> {
         instructions::withdraw::withdraw_cpi(_ctx)
     }
-    pub fn cancel_unstake<'info>(ctx: Context<'_, '_, '_, 'info, CancelUnstakeCpi<'info>>) -> Result<()> {
-        instructions::cancel_unstake::cancel_unstake_cpi(ctx)
+    pub fn instant_unstake(ctx: Context<InstantUnstake>, lst_amount: u64, min_amount_out: u64) -> Result<()> {
+        instructions::instant_unstake::instant_unstake(ctx, lst_amount, min_amount_out)
+    }
+    pub fn request_buffer_replenish<'info>(ctx: Context<'_, '_, '_, 'info, RequestBufferReplenish<'info>>) -> Result<()> {
+        instructions::buffer::request::request_buffer_replenish(ctx)
+    }
+    pub fn withdraw_buffer_replenish(ctx: Context<WithdrawBufferReplenish>) -> Result<()> {
+        instructions::buffer::withdraw::withdraw_buffer_replenish(ctx)
+    }
+    pub fn enqueue_unstake(ctx: Context<EnqueueUnstake>, lst_amount: u64, min_amount_out: u64) -> Result<()> {
+        instructions::unstake_queue::enqueue::enqueue_unstake(ctx, lst_amount, min_amount_out)
+    }
+    pub fn request_unstake_bucket<'info>(ctx: Context<'_, '_, '_, 'info, RequestUnstakeBucket<'info>>) -> Result<()> {
+        instructions::unstake_queue::request_bucket::request_unstake_bucket(ctx)
+    }
+    pub fn withdraw_unstake_bucket(ctx: Context<WithdrawUnstakeBucket>) -> Result<()> {
+        instructions::unstake_queue::withdraw_bucket::withdraw_unstake_bucket(ctx)
+    }
+    pub fn claim_unstake_ticket(ctx: Context<ClaimUnstakeTicket>) -> Result<()> {
+        instructions::unstake_queue::claim_ticket::claim_unstake_ticket(ctx)
+    }
+    pub fn migrate_unstake_request(ctx: Context<MigrateUnstakeRequest>) -> Result<()> {
+        instructions::migrate_unstake_request::migrate_unstake_request(ctx)
+    }
+    pub fn cancel_unstake<'info>(ctx: Context<'_, '_, '_, 'info, CancelUnstakeCpi<'info>>, min_liquid_out: u64) -> Result<()> {
+        instructions::cancel_unstake::cancel_unstake_cpi(ctx, min_liquid_out)
     }
     pub fn claim_fees<'info>(ctx: Context<'_, '_, '_, 'info, ClaimFeeCpi<'info>>) -> Result<()> {
         instructions::claim_fees::claim_fees_cpi(ctx)
     }
+    pub fn compound<'info>(ctx: Context<'_, '_, '_, 'info, CompoundCpi<'info>>, min_token_out: u64) -> Result<()> {
+        instructions::compound::compound_cpi(ctx, min_token_out)
+    }
+    pub fn initialize_staker_position(ctx: Context<InitializeStakerPosition>) -> Result<()> {
+        instructions::initialize_staker_position::initialize_staker_position(ctx)
+    }
+    pub fn initialize_quote_reward_checkpoint(ctx: Context<InitializeQuoteRewardCheckpoint>) -> Result<()> {
+        instructions::initialize_quote_reward_checkpoint::initialize_quote_reward_checkpoint(ctx)
+    }
+    pub fn settle_quote_rewards(ctx: Context<SettleQuoteRewards>, deposit_amount: u64, withdraw_amount: u64) -> Result<()> {
+        instructions::settle_quote_rewards::settle_quote_rewards(ctx, deposit_amount, withdraw_amount)
+    }
+    pub fn claim_quote_rewards(ctx: Context<ClaimQuoteRewards>) -> Result<()> {
+        instructions::claim_quote_rewards::claim_quote_rewards(ctx)
+    }
+    pub fn initialize_rate_history(ctx: Context<InitializeRateHistory>) -> Result<()> {
+        instructions::initialize_rate_history::initialize_rate_history(ctx)
+    }
+    pub fn get_exchange_rate(ctx: Context<GetExchangeRate>) -> Result<ExchangeRate> {
+        instructions::get_exchange_rate::get_exchange_rate(ctx)
+    }
+    pub fn update_pool_fees(ctx: Context<UpdatePoolFees>, deposit_fee_rate: u64, reward_fee_rate: u64) -> Result<()> {
+        instructions::update_pool_fees::update_pool_fees(ctx, deposit_fee_rate, reward_fee_rate)
+    }
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, token_amount: u64, quote_amount: u64) -> Result<()> {
+        instructions::withdraw_protocol_fees::withdraw_protocol_fees(ctx, token_amount, quote_amount)
+    }
+    pub fn set_pool_status(ctx: Context<SetPoolStatus>, status: u8) -> Result<()> {
+        instructions::set_pool_status::set_pool_status(ctx, status)
+    }
+    pub fn set_compound_route(ctx: Context<SetCompoundRoute>, swap_program: Pubkey, swap_pool: Pubkey, max_slippage_bps: u64) -> Result<()> {
+        instructions::set_compound_route::set_compound_route(ctx, swap_program, swap_pool, max_slippage_bps)
+    }
+    pub fn set_quote_reward_mode(ctx: Context<SetQuoteRewardMode>, distribute_quote_rewards: bool) -> Result<()> {
+        instructions::set_quote_reward_mode::set_quote_reward_mode(ctx, distribute_quote_rewards)
+    }
+    pub fn set_instant_unstake_params(ctx: Context<SetInstantUnstakeParams>, instant_unstake_fee_rate: u64, buffer_target: u64) -> Result<()> {
+        instructions::set_instant_unstake_params::set_instant_unstake_params(ctx, instant_unstake_fee_rate, buffer_target)
+    }
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_config::migrate_config(ctx)
+    }
+    pub fn set_fee_rate_caps(ctx: Context<SetFeeRateCaps>, max_deposit_fee_rate: u64, max_reward_fee_rate: u64) -> Result<()> {
+        instructions::set_fee_rate_caps::set_fee_rate_caps(ctx, max_deposit_fee_rate, max_reward_fee_rate)
+    }
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        instructions::set_guardian::set_guardian(ctx, guardian)
+    }
+    pub fn set_global_pause(ctx: Context<SetGlobalPause>, paused: bool) -> Result<()> {
+        instructions::set_global_pause::set_global_pause(ctx, paused)
+    }
+    pub fn propose_config_authority(ctx: Context<ProposeConfigAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::config_authority::propose::propose_config_authority(ctx, new_authority)
+    }
+    pub fn accept_config_authority(ctx: Context<AcceptConfigAuthority>) -> Result<()> {
+        instructions::config_authority::accept::accept_config_authority(ctx)
+    }
 
     pub fn create_address_lookup_table(ctx: Context<CreateLUT>) -> Result<()> {
         instructions::lut::create::create_lookup_table(ctx)
@@ -44,4 +129,19 @@ pub mod rememe {
     pub fn extend_address_lookup_table(ctx: Context<ExtendLUT>) -> Result<()> {
         instructions::lut::extend::extend_lut(ctx)
     }
+    pub fn populate_lookup_table(ctx: Context<PopulateLUT>) -> Result<()> {
+        instructions::lut::populate::populate_lut(ctx)
+    }
+    pub fn freeze_lookup_table(ctx: Context<FreezeLUT>) -> Result<()> {
+        instructions::lut::freeze::freeze_lut(ctx)
+    }
+    pub fn replace_lookup_table(ctx: Context<ReplaceLUT>) -> Result<()> {
+        instructions::lut::replace::replace_lut(ctx)
+    }
+    pub fn deactivate_lookup_table(ctx: Context<DeactivateLUT>) -> Result<()> {
+        instructions::lut::deactivate::deactivate_lut(ctx)
+    }
+    pub fn close_lookup_table(ctx: Context<CloseLUT>) -> Result<()> {
+        instructions::lut::close::close_lut(ctx)
+    }
 }
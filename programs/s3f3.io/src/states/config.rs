@@ -2,14 +2,33 @@ use anchor_lang::prelude::*;
 
 pub const CONFIG_SEED: &str = "config";
 
+/// Fee rates are expressed in percent of the amount they apply to
+pub const FEE_RATE_DENOMINATOR: u64 = 100;
+pub const DEFAULT_MAX_DEPOSIT_FEE_RATE: u64 = 5;
+pub const DEFAULT_MAX_REWARD_FEE_RATE: u64 = 30;
+pub const MAX_INSTANT_UNSTAKE_FEE_RATE: u64 = 10;
+
 #[account]
 #[derive(Default, Debug)]
 pub struct Config {
     pub creator_authority: Pubkey, 
     pub bump: u8,
-    pub padding: [u8; 32],
+    /// Stored in the 32 bytes the original layout reserved as padding
+    pub pending_authority: Pubkey,
+    pub max_deposit_fee_rate: u64,
+    pub max_reward_fee_rate: u64,
+    /// Can pause the protocol and disable pool operations, never re-enable them
+    pub guardian: Pubkey,
+    pub paused: bool,
+    pub padding: [u64; 8],
 }
 
 impl Config {
     pub const LEN: usize = 8 + std::mem::size_of::<Config>();
+    /// Account size before the fee caps and guardian fields
+    pub const LEGACY_LEN: usize = 8 + 32 + 1 + 32;
+
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        self.guardian != Pubkey::default() && self.guardian == *key
+    }
 }
@@ -1,3 +1,7 @@
 pub mod config;
 pub mod pool;
-pub mod unstake_request;
\ No newline at end of file
+pub mod unstake_request;
+pub mod rate_history;
+pub mod staker_position;
+pub mod quote_reward_checkpoint;
+pub mod unstake_queue;
@@ -1,16 +1,24 @@
 use anchor_lang::prelude::*;
 use std::ops::BitAnd;
+use crate::error::ErrorCode;
+use crate::utils::math;
 /// Seed to derive account address and signature
 pub const LIQUID_STAKING_POOL_SEED: &str = "liquid_staking_pool";
 pub const LIQUID_STAKING_POOL_MINT_SEED: &str = "liquid_staking_pool_mint";
 pub const LIQUID_STAKING_POOL_VAULT_SEED: &str = "liquid_staking_pool_vault";
 
 
+/// A set bit disables the operation
 pub enum LiquidStakingPoolStatusBitIndex {
     Deposit,
     Withdraw,
+    ClaimFees,
+    CancelUnstake,
+    Compound,
 }
 
+pub const LIQUID_STAKING_POOL_STATUS_MASK: u8 = 0b1_1111;
+
 #[derive(PartialEq, Eq)]
 pub enum LiquidStakingPoolStatusBitFlag {
     Enable,
@@ -18,7 +26,7 @@ pub enum LiquidStakingPoolStatusBitFlag {
 }
 
 #[account]
-#[derive(Debug)]
+#[derive(Default, Debug)]
 pub struct LiquidStakingPool {
     pub vault: Pubkey,
     pub token_mint: Pubkey,
@@ -39,13 +47,63 @@ pub struct LiquidStakingPool {
     pub protocol_fees_token: u64,
     pub protocol_fees_quote: u64,
 
+    /// Id of the open unstake queue bucket
     pub recent_epoch: u64,
     pub bump: u8,
     pub auth_bump: u8,
 
 
     pub status: u8,
-    pub padding: [u64; 64],
+
+    /// Meteora stake-for-fee accounts, recorded when the escrow is initialized
+    pub fee_pool: Pubkey,
+    pub stake_token_vault: Pubkey,
+    pub quote_token_vault: Pubkey,
+    pub top_staker_list: Pubkey,
+    pub full_balance_list: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lock_escrow: Pubkey,
+    pub escrow_vault: Pubkey,
+
+    /// Lookup table staged by `create_address_lookup_table` while `lut` is in use
+    pub pending_lut: Pubkey,
+    /// Lookup table replaced by `replace_lookup_table`, awaiting deactivation and close
+    pub retired_lut: Pubkey,
+
+    /// AMM program and pool `compound` swaps quote into the staking token through
+    pub swap_program: Pubkey,
+    pub swap_pool: Pubkey,
+
+    /// When set, quote rewards are paid to checkpointed LST instead of being compounded
+    pub distribute_quote_rewards: bool,
+    /// Cumulative quote reward per checkpointed LST, scaled by `REWARD_INDEX_PRECISION`
+    pub quote_reward_per_lst: u128,
+    /// Quote allocated to checkpoints and not yet claimed
+    pub quote_rewards_reserved: u64,
+    /// LST held in custody by quote reward checkpoints
+    pub checkpointed_lst: u64,
+
+    /// Exit fee of `instant_unstake`, in percent, kept in the pool for remaining holders
+    pub instant_unstake_fee_rate: u64,
+    /// Idle tokens the replenish crank keeps in `token_vault_account` for instant unstakes
+    pub buffer_target: u64,
+    /// Meteora unstake the crank has in flight to refill the buffer, and its amount
+    pub buffer_unstake: Pubkey,
+    pub buffer_unstake_amount: u64,
+
+    /// Tokens owed to the open unstake bucket, still staked in the escrow
+    pub queued_unstake_amount: u64,
+    /// Tokens withdrawn for unstake buckets, held in `token_vault_account` until claimed
+    pub claimable_unstake_amount: u64,
+    /// Set once the rate history exists, after which every sampling instruction must pass it
+    pub rate_history_initialized: bool,
+    /// Largest shortfall `compound` accepts against the AMM reserve quote, in basis points
+    pub max_compound_slippage_bps: u64,
+    /// Quote held back from checkpoints for `compound`: the balance when distribution was enabled,
+    /// and quote received while nothing was checkpointed
+    pub quote_compound_pending: u64,
+    /// Number of `claim_fees` runs; LST deposited into a checkpoint earns only once the count moves past its deposit
+    pub quote_fee_claims: u64,
 }
 
 
@@ -59,6 +117,206 @@ impl LiquidStakingPool {
         let status = u8::from(1) << (bit as u8);
         self.status.bitand(status) == 0
     }
+
+    /// Tokens backing the LST supply, counting the buffer unstake still in cooldown
+    /// and leaving out tokens owed to the unstake queue
+    pub fn total_backing(&self, token_vault_amount: u64, staked_amount: u64) -> Result<u64> {
+        let staked_amount = staked_amount.checked_add(self.buffer_unstake_amount).ok_or(ErrorCode::OverflowError)?;
+        math::total_backing(token_vault_amount, staked_amount, self.protocol_fees_token)?
+            .checked_sub(self.queued_unstake_amount)
+            .and_then(|amount| amount.checked_sub(self.claimable_unstake_amount))
+            .ok_or(error!(ErrorCode::MathUnderflow))
+    }
+
+    /// Protocol token fees that can leave the vault without touching tokens owed to claimable unstake tickets
+    pub fn withdrawable_protocol_fees_token(&self, token_vault_amount: u64) -> u64 {
+        self.protocol_fees_token.min(token_vault_amount.saturating_sub(self.claimable_unstake_amount))
+    }
+
+    /// Idle tokens available to instant unstakes
+    pub fn instant_unstake_buffer(&self, token_vault_amount: u64) -> u64 {
+        token_vault_amount
+            .saturating_sub(self.protocol_fees_token)
+            .saturating_sub(self.claimable_unstake_amount)
+    }
+
+    /// Quote in the pool vault that is neither protocol fees nor owed to checkpoints
+    pub fn undistributed_quote(&self, quote_vault_amount: u64) -> Result<u64> {
+        quote_vault_amount
+            .checked_sub(self.protocol_fees_quote)
+            .and_then(|amount| amount.checked_sub(self.quote_rewards_reserved))
+            .ok_or(error!(ErrorCode::MathUnderflow))
+    }
+
+    /// Quote `compound` may swap: all undistributed quote, or only the held back quote while distributing
+    pub fn compoundable_quote(&self, quote_vault_amount: u64) -> Result<u64> {
+        if self.distribute_quote_rewards {
+            Ok(self.quote_compound_pending)
+        } else {
+            self.undistributed_quote(quote_vault_amount)
+        }
+    }
+
+    /// Allocates undistributed quote to checkpointed LST when distribution is enabled.
+    /// With nothing checkpointed the quote is held back for `compound`, so the first checkpoint can't take it
+    pub fn accrue_quote_rewards(&mut self, quote_vault_amount: u64) -> Result<()> {
+        if !self.distribute_quote_rewards {
+            return Ok(());
+        }
+        let reward_amount = self.undistributed_quote(quote_vault_amount)?
+            .checked_sub(self.quote_compound_pending)
+            .ok_or(ErrorCode::MathUnderflow)?;
+        if reward_amount == 0 {
+            return Ok(());
+        }
+        if self.checkpointed_lst == 0 {
+            self.quote_compound_pending = self.quote_compound_pending.checked_add(reward_amount).ok_or(ErrorCode::OverflowError)?;
+            return Ok(());
+        }
+        let index_delta = math::reward_index_delta(reward_amount, self.checkpointed_lst)?;
+        self.quote_reward_per_lst = self.quote_reward_per_lst.checked_add(index_delta).ok_or(ErrorCode::OverflowError)?;
+        self.quote_rewards_reserved = self.quote_rewards_reserved.checked_add(reward_amount).ok_or(ErrorCode::OverflowError)?;
+        Ok(())
+    }
+
+    /// Returns quote forfeited by pending checkpoint LST from the reserve, holding it back for `compound`
+    pub fn release_quote_rewards(&mut self, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        self.quote_rewards_reserved = self.quote_rewards_reserved.checked_sub(amount).ok_or(ErrorCode::MathUnderflow)?;
+        if self.distribute_quote_rewards {
+            self.quote_compound_pending = self.quote_compound_pending.checked_add(amount).ok_or(ErrorCode::OverflowError)?;
+        }
+        Ok(())
+    }
+
+    /// Switches quote reward mode, allocating quote received so far under the old mode: disabling
+    /// first pays it to checkpoints, enabling holds the balance back for `compound`
+    pub fn set_distribute_quote_rewards(&mut self, distribute_quote_rewards: bool, quote_vault_amount: u64) -> Result<()> {
+        self.accrue_quote_rewards(quote_vault_amount)?;
+        if distribute_quote_rewards && !self.distribute_quote_rewards {
+            self.quote_compound_pending = self.undistributed_quote(quote_vault_amount)?;
+        } else if !distribute_quote_rewards {
+            self.quote_compound_pending = 0;
+        }
+        self.distribute_quote_rewards = distribute_quote_rewards;
+        Ok(())
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::quote_reward_checkpoint::QuoteRewardCheckpoint;
 
+    fn distributing_pool() -> LiquidStakingPool {
+        let mut pool = LiquidStakingPool::default();
+        pool.set_distribute_quote_rewards(true, 0).unwrap();
+        pool
+    }
+
+    fn checkpoint(pool: &mut LiquidStakingPool, lst_amount: u64, quote_vault_amount: u64) -> QuoteRewardCheckpoint {
+        pool.accrue_quote_rewards(quote_vault_amount).unwrap();
+        let mut checkpoint = QuoteRewardCheckpoint { reward_per_lst_paid: pool.quote_reward_per_lst, ..Default::default() };
+        checkpoint.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        checkpoint.lst_amount = lst_amount;
+        pool.checkpointed_lst += lst_amount;
+        checkpoint
+    }
+
+    #[test]
+    fn protocol_fee_withdrawal_leaves_claimable_unstakes() {
+        let pool = LiquidStakingPool { protocol_fees_token: 100, claimable_unstake_amount: 950, ..Default::default() };
+        assert_eq!(pool.withdrawable_protocol_fees_token(1_000), 50);
+        assert_eq!(pool.withdrawable_protocol_fees_token(900), 0);
+        assert_eq!(pool.withdrawable_protocol_fees_token(2_000), 100);
+    }
+
+    #[test]
+    fn quote_is_split_by_checkpointed_lst() {
+        let mut pool = distributing_pool();
+        let mut small = checkpoint(&mut pool, 100, 0);
+        let mut large = checkpoint(&mut pool, 300, 0);
+
+        pool.accrue_quote_rewards(400).unwrap();
+        small.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        large.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        assert_eq!(small.accrued_quote, 100);
+        assert_eq!(large.accrued_quote, 300);
+        assert_eq!(pool.quote_rewards_reserved, 400);
+        assert_eq!(pool.undistributed_quote(400).unwrap(), 0);
+
+        // Settling again at the same index pays nothing twice
+        small.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        assert_eq!(small.accrued_quote, 100);
+    }
+
+    #[test]
+    fn late_checkpoint_earns_only_later_quote() {
+        let mut pool = distributing_pool();
+        let mut early = checkpoint(&mut pool, 100, 0);
+        let mut late = checkpoint(&mut pool, 100, 200);
+
+        pool.accrue_quote_rewards(400).unwrap();
+        early.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        late.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        assert_eq!(early.accrued_quote, 300);
+        assert_eq!(late.accrued_quote, 100);
+    }
+
+    #[test]
+    fn checkpoint_before_a_fee_claim_forfeits_it_to_compound() {
+        let mut pool = distributing_pool();
+        let mut early = checkpoint(&mut pool, 100, 0);
+        let mut late = QuoteRewardCheckpoint::default();
+        late.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        late.deposit(100, pool.quote_reward_per_lst, pool.quote_fee_claims, 0).unwrap();
+        pool.checkpointed_lst += 100;
+
+        // Fees that accrued before the late deposit land with the next claim
+        pool.accrue_quote_rewards(400).unwrap();
+        pool.quote_fee_claims += 1;
+        early.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        let forfeited = late.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        pool.release_quote_rewards(forfeited).unwrap();
+        assert_eq!(early.accrued_quote, 200);
+        assert_eq!(late.accrued_quote, 0);
+        assert_eq!(pool.quote_compound_pending, 200);
+        assert_eq!(pool.quote_rewards_reserved, 200);
+
+        pool.accrue_quote_rewards(600).unwrap();
+        early.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        late.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        assert_eq!(early.accrued_quote, 300);
+        assert_eq!(late.accrued_quote, 100);
+    }
+
+    #[test]
+    fn quote_before_any_checkpoint_is_held_for_compound() {
+        let mut pool = distributing_pool();
+        let mut first = checkpoint(&mut pool, 100, 500);
+        assert_eq!(pool.quote_compound_pending, 500);
+
+        pool.accrue_quote_rewards(600).unwrap();
+        first.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        assert_eq!(first.accrued_quote, 100);
+        assert_eq!(pool.compoundable_quote(600).unwrap(), 500);
+    }
+
+    #[test]
+    fn enabling_distribution_holds_back_the_existing_balance() {
+        let mut pool = LiquidStakingPool { protocol_fees_quote: 10, ..Default::default() };
+        pool.set_distribute_quote_rewards(true, 110).unwrap();
+        assert_eq!(pool.quote_compound_pending, 100);
+
+        let mut holder = checkpoint(&mut pool, 100, 110);
+        pool.accrue_quote_rewards(160).unwrap();
+        holder.settle(pool.quote_reward_per_lst, pool.quote_fee_claims).unwrap();
+        assert_eq!(holder.accrued_quote, 50);
+
+        pool.set_distribute_quote_rewards(false, 160).unwrap();
+        assert_eq!(pool.quote_compound_pending, 0);
+        assert_eq!(pool.compoundable_quote(160).unwrap(), 100);
+    }
+}
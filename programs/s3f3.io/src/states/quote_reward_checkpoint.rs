@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::utils::math;
+
+pub const QUOTE_REWARD_CHECKPOINT_SEED: &str = "quote_reward_checkpoint";
+
+/// Seconds LST stays in checkpoint custody after a deposit, so it can't be parked just around a fee claim
+pub const MIN_CHECKPOINT_DURATION: i64 = 86_400;
+
+/// LST a holder keeps in pool custody to earn quote rewards, and the rewards owed on it
+#[account]
+#[derive(Default, Debug)]
+pub struct QuoteRewardCheckpoint {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub lst_amount: u64,
+    /// Pool `quote_reward_per_lst` at the last settle
+    pub reward_per_lst_paid: u128,
+    pub accrued_quote: u64,
+    pub total_claimed_quote: u64,
+    /// Unix timestamp of the last deposit into custody
+    pub last_deposit_ts: i64,
+    pub bump: u8,
+    /// Part of `lst_amount` deposited since the last `claim_fees`. It earns nothing until the pool's
+    /// `quote_fee_claims` moves past `pending_fee_claims`, so a deposit can't share in fees that accrued before it
+    pub pending_lst_amount: u64,
+    /// Pool `quote_reward_per_lst` when the pending LST was last settled
+    pub pending_reward_per_lst: u128,
+    /// Pool `quote_fee_claims` at the last deposit
+    pub pending_fee_claims: u64,
+    pub padding: [u64; 3],
+}
+
+impl QuoteRewardCheckpoint {
+    pub const LEN: usize = 8 + std::mem::size_of::<QuoteRewardCheckpoint>();
+
+    /// Credits rewards earned since the last settle at the pool's current index. Pending LST starts earning
+    /// once a `claim_fees` has run since its deposit; what it accrued until then is returned as forfeited
+    pub fn settle(&mut self, reward_per_lst: u128, fee_claims: u64) -> Result<u64> {
+        let active_lst = self.lst_amount.checked_sub(self.pending_lst_amount).ok_or(ErrorCode::MathUnderflow)?;
+        let index_delta = reward_per_lst.checked_sub(self.reward_per_lst_paid).ok_or(ErrorCode::MathUnderflow)?;
+        let earned = math::accrued_rewards(active_lst, index_delta)?;
+        self.accrued_quote = self.accrued_quote.checked_add(earned).ok_or(ErrorCode::OverflowError)?;
+        self.reward_per_lst_paid = reward_per_lst;
+        if self.pending_lst_amount == 0 || fee_claims <= self.pending_fee_claims {
+            return Ok(0);
+        }
+        let forfeited = self.forfeit_pending(reward_per_lst)?;
+        self.pending_lst_amount = 0;
+        Ok(forfeited)
+    }
+
+    /// Adds `amount` to custody as pending LST. Call after `settle`; returns what the earlier pending LST forfeits
+    pub fn deposit(&mut self, amount: u64, reward_per_lst: u128, fee_claims: u64, now: i64) -> Result<u64> {
+        let forfeited = self.forfeit_pending(reward_per_lst)?;
+        self.lst_amount = self.lst_amount.checked_add(amount).ok_or(ErrorCode::OverflowError)?;
+        self.pending_lst_amount = self.pending_lst_amount.checked_add(amount).ok_or(ErrorCode::OverflowError)?;
+        self.pending_fee_claims = fee_claims;
+        self.last_deposit_ts = now;
+        Ok(forfeited)
+    }
+
+    /// Takes `amount` out of custody, pending LST first. Call after `settle`; returns what the pending LST forfeits
+    pub fn withdraw(&mut self, amount: u64, reward_per_lst: u128, now: i64) -> Result<u64> {
+        self.check_unlocked(now)?;
+        let forfeited = self.forfeit_pending(reward_per_lst)?;
+        self.lst_amount = self.lst_amount.checked_sub(amount).ok_or(ErrorCode::InsufficientLiquidStakingToken)?;
+        self.pending_lst_amount = self.pending_lst_amount.saturating_sub(amount);
+        Ok(forfeited)
+    }
+
+    /// Rewards the pending LST accrued since it was last settled, which it never earns
+    fn forfeit_pending(&mut self, reward_per_lst: u128) -> Result<u64> {
+        let index_delta = reward_per_lst.checked_sub(self.pending_reward_per_lst).ok_or(ErrorCode::MathUnderflow)?;
+        let forfeited = math::accrued_rewards(self.pending_lst_amount, index_delta)?;
+        self.pending_reward_per_lst = reward_per_lst;
+        Ok(forfeited)
+    }
+
+    /// Fails while the last deposit is younger than `MIN_CHECKPOINT_DURATION`
+    pub fn check_unlocked(&self, now: i64) -> Result<()> {
+        let unlock_ts = self.last_deposit_ts.checked_add(MIN_CHECKPOINT_DURATION).ok_or(ErrorCode::OverflowError)?;
+        if now < unlock_ts {
+            return err!(ErrorCode::CheckpointLocked);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settle_credits_only_the_index_increase() {
+        let mut checkpoint = QuoteRewardCheckpoint { lst_amount: 2_000, ..Default::default() };
+        checkpoint.settle(3 * math::REWARD_INDEX_PRECISION, 0).unwrap();
+        checkpoint.settle(3 * math::REWARD_INDEX_PRECISION, 0).unwrap();
+        assert_eq!(checkpoint.accrued_quote, 6_000);
+        assert!(checkpoint.settle(math::REWARD_INDEX_PRECISION, 0).is_err());
+    }
+
+    #[test]
+    fn deposit_earns_only_after_the_next_fee_claim() {
+        let mut checkpoint = QuoteRewardCheckpoint::default();
+        assert_eq!(checkpoint.deposit(1_000, 0, 5, 0).unwrap(), 0);
+
+        // Fees claimed by the sixth `claim_fees` accrued before the deposit, so its share is forfeited
+        assert_eq!(checkpoint.settle(math::REWARD_INDEX_PRECISION, 5).unwrap(), 0);
+        assert_eq!(checkpoint.settle(2 * math::REWARD_INDEX_PRECISION, 6).unwrap(), 2_000);
+        assert_eq!(checkpoint.accrued_quote, 0);
+        assert_eq!(checkpoint.pending_lst_amount, 0);
+
+        checkpoint.settle(3 * math::REWARD_INDEX_PRECISION, 7).unwrap();
+        assert_eq!(checkpoint.accrued_quote, 1_000);
+    }
+
+    #[test]
+    fn withdrawal_takes_pending_lst_first() {
+        let mut checkpoint = QuoteRewardCheckpoint { lst_amount: 1_000, ..Default::default() };
+        checkpoint.deposit(500, 0, 0, 0).unwrap();
+        checkpoint.settle(math::REWARD_INDEX_PRECISION, 0).unwrap();
+        assert_eq!(checkpoint.withdraw(600, math::REWARD_INDEX_PRECISION, MIN_CHECKPOINT_DURATION).unwrap(), 500);
+        assert_eq!(checkpoint.lst_amount, 900);
+        assert_eq!(checkpoint.pending_lst_amount, 0);
+        assert_eq!(checkpoint.accrued_quote, 1_000);
+    }
+
+    #[test]
+    fn withdrawal_waits_out_the_minimum_duration() {
+        let checkpoint = QuoteRewardCheckpoint { last_deposit_ts: 1_000, ..Default::default() };
+        assert!(checkpoint.check_unlocked(1_000 + MIN_CHECKPOINT_DURATION - 1).is_err());
+        assert!(checkpoint.check_unlocked(1_000 + MIN_CHECKPOINT_DURATION).is_ok());
+    }
+}
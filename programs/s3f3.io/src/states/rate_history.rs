@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use crate::utils::math;
+
+pub const RATE_HISTORY_SEED: &str = "rate_history";
+pub const RATE_HISTORY_CAPACITY: usize = 64;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug)]
+pub struct RateSample {
+    pub slot: u64,
+    pub timestamp: i64,
+    pub total_backing: u64,
+    pub liquid_supply: u64,
+}
+
+impl RateSample {
+    pub fn rate(&self) -> Option<u64> {
+        math::exchange_rate(self.total_backing, self.liquid_supply).ok()
+    }
+}
+
+#[account]
+#[derive(Debug)]
+pub struct RateHistory {
+    pub pool: Pubkey,
+    pub bump: u8,
+    /// Index the next sample is written to
+    pub head: u16,
+    pub len: u16,
+    pub samples: [RateSample; RATE_HISTORY_CAPACITY],
+}
+
+impl RateHistory {
+    pub const LEN: usize = 8 + std::mem::size_of::<RateHistory>();
+
+    /// Records a sample, replacing the latest one if it was taken in the same slot
+    pub fn record(&mut self, clock: &Clock, total_backing: u64, liquid_supply: u64) {
+        let sample = RateSample {
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+            total_backing,
+            liquid_supply,
+        };
+        if let Some(latest) = self.latest_index() {
+            if self.samples[latest].slot == sample.slot {
+                self.samples[latest] = sample;
+                return;
+            }
+        }
+        self.samples[self.head as usize] = sample;
+        self.head = ((self.head as usize + 1) % RATE_HISTORY_CAPACITY) as u16;
+        self.len = (self.len + 1).min(RATE_HISTORY_CAPACITY as u16);
+    }
+
+    pub fn latest(&self) -> Option<&RateSample> {
+        self.latest_index().map(|index| &self.samples[index])
+    }
+
+    /// Samples from oldest to newest
+    pub fn iter(&self) -> impl Iterator<Item = &RateSample> {
+        let start = (self.head as usize + RATE_HISTORY_CAPACITY - self.len as usize) % RATE_HISTORY_CAPACITY;
+        (0..self.len as usize).map(move |offset| &self.samples[(start + offset) % RATE_HISTORY_CAPACITY])
+    }
+
+    /// Time-weighted average rate over `[now - window, now]`, each sample's rate holding until the next one.
+    /// Only the recorded range is averaged if the history does not reach back to the window start.
+    pub fn time_weighted_average_rate(&self, now: i64, window: i64) -> Option<u64> {
+        let window_start = now.checked_sub(window)?;
+        let mut weighted_sum: u128 = 0;
+        let mut total_duration: u128 = 0;
+        let mut samples = self.iter().peekable();
+        while let Some(sample) = samples.next() {
+            let end = samples.peek().map_or(now, |next| next.timestamp).min(now);
+            let start = sample.timestamp.max(window_start);
+            if end <= start {
+                continue;
+            }
+            let duration = (end - start) as u128;
+            weighted_sum = weighted_sum.checked_add((sample.rate()? as u128).checked_mul(duration)?)?;
+            total_duration += duration;
+        }
+        if total_duration == 0 {
+            return self.latest()?.rate();
+        }
+        u64::try_from(weighted_sum / total_duration).ok()
+    }
+
+    fn latest_index(&self) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        Some((self.head as usize + RATE_HISTORY_CAPACITY - 1) % RATE_HISTORY_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> RateHistory {
+        RateHistory {
+            pool: Pubkey::default(),
+            bump: 0,
+            head: 0,
+            len: 0,
+            samples: [RateSample::default(); RATE_HISTORY_CAPACITY],
+        }
+    }
+
+    fn clock(slot: u64, unix_timestamp: i64) -> Clock {
+        Clock {
+            slot,
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn record_wraps_around_keeping_the_newest_samples() {
+        let mut history = history();
+        let total = RATE_HISTORY_CAPACITY as u64 + 5;
+        for slot in 1..=total {
+            history.record(&clock(slot, slot as i64), slot, 1);
+        }
+        assert_eq!(history.len as usize, RATE_HISTORY_CAPACITY);
+        assert_eq!(history.head, 5);
+        let slots: Vec<u64> = history.iter().map(|sample| sample.slot).collect();
+        assert_eq!(slots.len(), RATE_HISTORY_CAPACITY);
+        assert_eq!(slots[0], 6);
+        assert_eq!(*slots.last().unwrap(), total);
+        assert!(slots.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(history.latest().unwrap().slot, total);
+    }
+
+    #[test]
+    fn small_pool_samples_keep_their_rate() {
+        let sample = RateSample { total_backing: 500_000_000, liquid_supply: 500_000_000, ..RateSample::default() };
+        assert_eq!(sample.rate(), Some(math::EXCHANGE_RATE_PRECISION));
+    }
+
+    #[test]
+    fn record_replaces_sample_from_the_same_slot() {
+        let mut history = history();
+        history.record(&clock(7, 70), 100, 100);
+        history.record(&clock(7, 70), 300, 100);
+        assert_eq!(history.len, 1);
+        assert_eq!(history.latest().unwrap().total_backing, 300);
+    }
+
+    #[test]
+    fn time_weighted_average_rate_weights_by_duration() {
+        let mut history = history();
+        history.record(&clock(1, 0), 1_000_000_000, 1_000_000_000);
+        history.record(&clock(2, 100), 3_000_000_000, 1_000_000_000);
+        let first = history.iter().next().unwrap().rate().unwrap();
+        let second = history.latest().unwrap().rate().unwrap();
+
+        assert_eq!(history.time_weighted_average_rate(400, 400).unwrap(), (first + 3 * second) / 4);
+        // Only the part of the window after the last sample sees its rate
+        assert_eq!(history.time_weighted_average_rate(400, 50).unwrap(), second);
+    }
+
+    #[test]
+    fn time_weighted_average_rate_of_empty_history_is_none() {
+        assert!(history().time_weighted_average_rate(100, 100).is_none());
+    }
+}
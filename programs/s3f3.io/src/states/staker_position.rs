@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+pub const STAKER_POSITION_SEED: &str = "staker_position";
+
+/// Lifetime accounting of a staker in a pool, all amounts are cumulative
+#[account]
+#[derive(Default, Debug)]
+pub struct StakerPosition {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    /// Tokens staked, including deposit fees
+    pub total_deposited: u64,
+    pub total_deposit_fees: u64,
+    pub total_lst_minted: u64,
+    pub total_lst_burned: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+    pub padding: [u64; 16],
+}
+
+impl StakerPosition {
+    pub const LEN: usize = 8 + std::mem::size_of::<StakerPosition>();
+
+    pub fn record_deposit(&mut self, amount: u64, deposit_fee: u64, lst_minted: u64) -> Result<()> {
+        self.total_deposited = self.total_deposited.checked_add(amount).ok_or(ErrorCode::OverflowError)?;
+        self.total_deposit_fees = self.total_deposit_fees.checked_add(deposit_fee).ok_or(ErrorCode::OverflowError)?;
+        self.record_lst_minted(lst_minted)
+    }
+
+    pub fn record_lst_minted(&mut self, lst_minted: u64) -> Result<()> {
+        self.total_lst_minted = self.total_lst_minted.checked_add(lst_minted).ok_or(ErrorCode::OverflowError)?;
+        Ok(())
+    }
+
+    pub fn record_lst_burned(&mut self, lst_burned: u64) -> Result<()> {
+        self.total_lst_burned = self.total_lst_burned.checked_add(lst_burned).ok_or(ErrorCode::OverflowError)?;
+        Ok(())
+    }
+
+    pub fn record_withdraw(&mut self, amount: u64) -> Result<()> {
+        self.total_withdrawn = self.total_withdrawn.checked_add(amount).ok_or(ErrorCode::OverflowError)?;
+        Ok(())
+    }
+}
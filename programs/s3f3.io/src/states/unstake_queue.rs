@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+pub const UNSTAKE_BUCKET_SEED: &str = "unstake_bucket";
+pub const UNSTAKE_TICKET_SEED: &str = "unstake_ticket";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum UnstakeBucketStatus {
+    /// Taking enqueued unstakes, the tokens are still staked
+    #[default]
+    Open,
+    /// One Meteora unstake was requested for the whole bucket
+    Requested,
+    /// The unstake was withdrawn into the pool token vault, tickets can be claimed
+    Withdrawn,
+}
+
+/// Unstakes enqueued while `LiquidStakingPool::recent_epoch` pointed at this bucket
+#[account]
+#[derive(Default, Debug)]
+pub struct UnstakeBucket {
+    pub pool: Pubkey,
+    pub id: u64,
+    /// Solana epoch the bucket was opened in, it is requested once that epoch is over
+    pub opened_epoch: u64,
+    pub status: UnstakeBucketStatus,
+    pub unstake: Pubkey,
+    pub lst_amount: u64,
+    /// Tokens owed to the tickets at their enqueue rates
+    pub token_amount: u64,
+    /// Tokens received from the Meteora withdraw, shared pro rata between tickets
+    pub withdrawn_amount: u64,
+    pub bump: u8,
+    /// Sum of `token_amount` over the tickets claimed so far, and the tokens paid to them
+    pub claimed_token_amount: u64,
+    pub claimed_amount: u64,
+    pub padding: [u64; 6],
+}
+
+impl UnstakeBucket {
+    pub const LEN: usize = 8 + std::mem::size_of::<UnstakeBucket>();
+
+    /// Records a ticket claim. Once every ticket is claimed, returns the rounding dust
+    /// left of `withdrawn_amount`, which no ticket will ever claim
+    pub fn record_claim(&mut self, ticket_token_amount: u64, amount: u64) -> Result<u64> {
+        self.claimed_token_amount = self.claimed_token_amount.checked_add(ticket_token_amount).ok_or(ErrorCode::OverflowError)?;
+        self.claimed_amount = self.claimed_amount.checked_add(amount).ok_or(ErrorCode::OverflowError)?;
+        if self.claimed_token_amount < self.token_amount {
+            return Ok(0);
+        }
+        self.withdrawn_amount.checked_sub(self.claimed_amount).ok_or(error!(ErrorCode::MathUnderflow))
+    }
+}
+
+/// A staker's share of an unstake bucket
+#[account]
+#[derive(Default, Debug)]
+pub struct UnstakeTicket {
+    pub pool: Pubkey,
+    pub bucket: Pubkey,
+    pub owner: Pubkey,
+    pub lst_amount: u64,
+    pub token_amount: u64,
+    pub bump: u8,
+}
+
+impl UnstakeTicket {
+    pub const LEN: usize = 8 + std::mem::size_of::<UnstakeTicket>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::math;
+
+    #[test]
+    fn last_claim_sweeps_the_rounding_dust() {
+        let mut bucket = UnstakeBucket { token_amount: 300, withdrawn_amount: 200, ..Default::default() };
+        for _ in 0..2 {
+            let amount = math::pro_rata(100, bucket.withdrawn_amount, bucket.token_amount).unwrap();
+            assert_eq!(amount, 66);
+            assert_eq!(bucket.record_claim(100, amount).unwrap(), 0);
+        }
+        assert_eq!(bucket.record_claim(100, 66).unwrap(), 2);
+    }
+}
@@ -1,12 +1,67 @@
 use anchor_lang::prelude::*;
 
+pub const LST_UNSTAKE_REQUEST_SEED: &str = "lst_unstake_request";
+pub const UNSTAKE_REQUEST_COUNTER_SEED: &str = "unstake_request_counter";
+pub const UNSTAKE_TICKET_MINT_SEED: &str = "unstake_ticket_mint";
+
+pub const UNSTAKE_TICKET_NAME: &str = "LST Unstake Ticket";
+pub const UNSTAKE_TICKET_SYMBOL: &str = "LSTUT";
+
+/// Requests created by `request_unstake` live at `[LST_UNSTAKE_REQUEST_SEED, pool, owner, nonce]`.
+/// Requests migrated from the legacy layout keep their keypair address, with zero nonce,
+/// LST amount, rate and creation time.
 #[account]
 pub struct LstUnstakeRequest {
+    /// Staker that requested the unstake.
+    /// Withdraw and cancel are authorized by the `ticket_mint` holder instead.
     pub owner: Pubkey,
     pub unstake: Pubkey,
-    pub padding: [u64; 20],
+    pub pool: Pubkey,
+    pub nonce: u64,
+    /// LST burned for this request
+    pub lst_amount: u64,
+    /// Tokens requested from the stake escrow
+    pub token_amount: u64,
+    /// Tokens per `EXCHANGE_RATE_PRECISION` LST at request time
+    pub exchange_rate: u64,
+    pub created_slot: u64,
+    pub created_at: i64,
+    pub bump: u8,
+    /// 1-of-1 ticket minted to the staker, default for requests made before tickets
+    pub ticket_mint: Pubkey,
+    pub padding: [u64; 12],
 }
 
 impl LstUnstakeRequest {
     pub const LEN: usize = 8 + std::mem::size_of::<LstUnstakeRequest>();
+
+    pub fn has_ticket(&self) -> bool {
+        self.ticket_mint != Pubkey::default()
+    }
+}
+
+/// Layout of requests created at keypair addresses before they were derived from
+/// (pool, owner, nonce), grown in place by `migrate_unstake_request`
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyLstUnstakeRequest {
+    pub owner: Pubkey,
+    pub unstake: Pubkey,
+    pub padding: [u64; 20],
+}
+
+impl LegacyLstUnstakeRequest {
+    pub const LEN: usize = 8 + 32 + 32 + 8 * 20;
+}
+
+/// Per (pool, owner) nonce source, requests live at nonces `0..next_nonce`
+#[account]
+pub struct UnstakeRequestCounter {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub next_nonce: u64,
+    pub bump: u8,
+}
+
+impl UnstakeRequestCounter {
+    pub const LEN: usize = 8 + std::mem::size_of::<UnstakeRequestCounter>();
 }
@@ -0,0 +1,33 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use solana_address_lookup_table_program::state::AddressLookupTable;
+
+/// Number of Meteora accounts the CPI handlers read from the lookup table:
+/// a_vault, b_vault, a_vault_lp, b_vault_lp, a_vault_lp_mint, b_vault_lp_mint,
+/// a_token_vault, b_token_vault, amm_program, vault_program, event_authority
+pub const METEORA_LOOKUP_TABLE_LEN: usize = 11;
+
+/// Checks `lookup_table` is the pool's table and that the leading remaining accounts
+/// match its addresses one by one. Returns the table accounts and the leftover accounts.
+pub fn split_lookup_table_accounts<'a, 'info>(
+    lookup_table: &AccountInfo<'info>,
+    pool_lut: &Pubkey,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    if lookup_table.key() != *pool_lut {
+        return err!(ErrorCode::InvalidLookupTable);
+    }
+    let alt_bytes = lookup_table.try_borrow_data()?;
+    let table = AddressLookupTable::deserialize(&alt_bytes).map_err(|_| ErrorCode::InvalidLookupTableData)?;
+    let lookup_table_len = table.addresses.len();
+    if lookup_table_len < METEORA_LOOKUP_TABLE_LEN {
+        return err!(ErrorCode::LookupTableTooShort);
+    }
+    let table_infos = remaining_accounts.get(..lookup_table_len).ok_or(ErrorCode::InvalidNumberOfAccounts)?;
+    for (info, address) in table_infos.iter().zip(table.addresses.iter()) {
+        if info.key() != *address {
+            return err!(ErrorCode::LookupTableAccountMismatch);
+        }
+    }
+    Ok((table_infos, &remaining_accounts[lookup_table_len..]))
+}
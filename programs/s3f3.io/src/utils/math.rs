@@ -0,0 +1,271 @@
+use crate::error::ErrorCode;
+use crate::states::config::FEE_RATE_DENOMINATOR;
+use anchor_lang::prelude::*;
+
+/// Virtual LST supply and token backing added to both sides of every conversion.
+/// Donating tokens straight into the vault mostly accrues to these virtual shares,
+/// so inflating the rate against the next depositor always costs the donor.
+pub const VIRTUAL_LIQUID_SUPPLY: u64 = 1_000_000;
+pub const VIRTUAL_TOKEN_AMOUNT: u64 = 1_000_000;
+
+/// LST base units the exchange rate is quoted for: one whole LST, as `initialize_pool`
+/// creates every LST mint with 9 decimals.
+pub const EXCHANGE_RATE_PRECISION: u64 = 1_000_000_000;
+
+/// Scale of the cumulative quote reward per LST index
+pub const REWARD_INDEX_PRECISION: u128 = 1_000_000_000_000;
+
+/// Basis points in one whole, used by the compound slippage bound
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Largest share of the AMM quote reserve a single `compound` swaps, in basis points,
+/// bounding the price impact a sandwich around one call can extract
+pub const MAX_COMPOUND_RESERVE_BPS: u64 = 100;
+
+/// Tokens backing the LST supply: idle vault balance plus escrow stake, net of protocol fees
+pub fn total_backing(token_vault_amount: u64, staked_amount: u64, protocol_fees_token: u64) -> Result<u64> {
+    token_vault_amount
+        .checked_add(staked_amount)
+        .ok_or(ErrorCode::OverflowError)?
+        .checked_sub(protocol_fees_token)
+        .ok_or(error!(ErrorCode::MathUnderflow))
+}
+
+/// Fee charged at `fee_rate` percent of `amount`, rounded down
+pub fn fee_amount(amount: u64, fee_rate: u64) -> Result<u64> {
+    mul_div_floor(amount, fee_rate as u128, FEE_RATE_DENOMINATOR as u128)
+}
+
+/// LST minted for depositing `token_amount`, rounded down so the depositor never gets more than paid for
+pub fn token_to_lst(token_amount: u64, total_backing: u64, liquid_supply: u64) -> Result<u64> {
+    mul_div_floor(
+        token_amount,
+        liquid_supply as u128 + VIRTUAL_LIQUID_SUPPLY as u128,
+        total_backing as u128 + VIRTUAL_TOKEN_AMOUNT as u128,
+    )
+}
+
+/// Tokens owed for burning `liquid_amount`, rounded down and capped at the real backing
+/// so the virtual assets can never be paid out
+pub fn lst_to_token(liquid_amount: u64, total_backing: u64, liquid_supply: u64) -> Result<u64> {
+    let token_amount = mul_div_floor(
+        liquid_amount,
+        total_backing as u128 + VIRTUAL_TOKEN_AMOUNT as u128,
+        liquid_supply as u128 + VIRTUAL_LIQUID_SUPPLY as u128,
+    )?;
+    Ok(token_amount.min(total_backing))
+}
+
+/// Staking token base units per `EXCHANGE_RATE_PRECISION` LST, over the same virtual offsets as the
+/// conversions. Unlike `lst_to_token` it is not capped at the backing, so small pools quote their real rate.
+pub fn exchange_rate(total_backing: u64, liquid_supply: u64) -> Result<u64> {
+    mul_div_floor(
+        EXCHANGE_RATE_PRECISION,
+        total_backing as u128 + VIRTUAL_TOKEN_AMOUNT as u128,
+        liquid_supply as u128 + VIRTUAL_LIQUID_SUPPLY as u128,
+    )
+}
+
+/// Increase of the reward per LST index when `reward_amount` is split over `lst_amount`
+pub fn reward_index_delta(reward_amount: u64, lst_amount: u64) -> Result<u128> {
+    (reward_amount as u128)
+        .checked_mul(REWARD_INDEX_PRECISION)
+        .ok_or(ErrorCode::OverflowError)?
+        .checked_div(lst_amount as u128)
+        .ok_or(error!(ErrorCode::DivisionError))
+}
+
+/// Rewards earned by `lst_amount` over an index increase, rounded down
+pub fn accrued_rewards(lst_amount: u64, index_delta: u128) -> Result<u64> {
+    let result = (lst_amount as u128)
+        .checked_mul(index_delta)
+        .ok_or(ErrorCode::OverflowError)?
+        / REWARD_INDEX_PRECISION;
+    u64::try_from(result).map_err(|_| error!(ErrorCode::OverflowError))
+}
+
+/// Share of `received` owed to `amount` out of `total`, rounded down
+pub fn pro_rata(amount: u64, received: u64, total: u64) -> Result<u64> {
+    mul_div_floor(amount, received as u128, total as u128)
+}
+
+/// Lowest acceptable output of a compound swap: the constant-product quote for `quote_in`
+/// against the AMM reserves, less `max_slippage_bps`
+pub fn compound_min_out(quote_in: u64, quote_reserve: u64, token_reserve: u64, max_slippage_bps: u64) -> Result<u64> {
+    if quote_reserve == 0 || token_reserve == 0 {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+    let reserve_after = (quote_reserve as u128)
+        .checked_add(quote_in as u128)
+        .ok_or(ErrorCode::OverflowError)?;
+    let expected_out = mul_div_floor(token_reserve, quote_in as u128, reserve_after)?;
+    let keep_bps = BPS_DENOMINATOR.checked_sub(max_slippage_bps).ok_or(ErrorCode::MathUnderflow)?;
+    mul_div_floor(expected_out, keep_bps as u128, BPS_DENOMINATOR as u128)
+}
+
+/// Quote one `compound` swaps: the compoundable quote, capped at `MAX_COMPOUND_RESERVE_BPS` of the AMM quote reserve
+pub fn compound_swap_amount(compoundable: u64, quote_reserve: u64) -> Result<u64> {
+    let cap = mul_div_floor(quote_reserve, MAX_COMPOUND_RESERVE_BPS as u128, BPS_DENOMINATOR as u128)?;
+    Ok(compoundable.min(cap))
+}
+
+fn mul_div_floor(amount: u64, numerator: u128, denominator: u128) -> Result<u64> {
+    let result = (amount as u128)
+        .checked_mul(numerator)
+        .ok_or(ErrorCode::OverflowError)?
+        .checked_div(denominator)
+        .ok_or(ErrorCode::DivisionError)?;
+    u64::try_from(result).map_err(|_| error!(ErrorCode::OverflowError))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use quickcheck::{quickcheck, TestResult};
+
+    const MAX_AMOUNT: u64 = 1_000_000_000_000_000;
+
+    proptest! {
+        #[test]
+        fn donation_does_not_profit_attacker(
+            attacker_deposit in 1u64..1_000_000_000_000,
+            donation in 0u64..MAX_AMOUNT,
+            victim_deposit in 1u64..MAX_AMOUNT,
+        ) {
+            let attacker_shares = token_to_lst(attacker_deposit, 0, 0).unwrap();
+            let backing = attacker_deposit + donation;
+            let victim_shares = token_to_lst(victim_deposit, backing, attacker_shares).unwrap();
+            // Price of one share when the victim deposits, rounded up
+            let share_price = (backing + VIRTUAL_TOKEN_AMOUNT).div_ceil(attacker_shares + VIRTUAL_LIQUID_SUPPLY);
+
+            let backing = backing + victim_deposit;
+            let supply = attacker_shares + victim_shares;
+            let attacker_out = lst_to_token(attacker_shares, backing, supply).unwrap();
+            // Whatever the donation, the attacker never gets back more than it put in
+            prop_assert!(attacker_out <= attacker_deposit + donation);
+
+            let victim_out = lst_to_token(victim_shares, backing - attacker_out, supply - attacker_shares).unwrap();
+            // The victim loses at most one share's worth to the mint rounding, plus one base unit on redeem
+            prop_assert!(victim_out + share_price + 1 >= victim_deposit);
+        }
+
+        #[test]
+        fn deposit_then_redeem_never_gains(
+            backing in 0u64..MAX_AMOUNT,
+            supply in 0u64..MAX_AMOUNT,
+            deposit in 0u64..MAX_AMOUNT,
+        ) {
+            let shares = token_to_lst(deposit, backing, supply).unwrap();
+            let redeemed = lst_to_token(shares, backing + deposit, supply + shares).unwrap();
+            prop_assert!(redeemed <= deposit);
+        }
+
+        #[test]
+        fn redeem_then_deposit_never_gains(
+            backing in 0u64..MAX_AMOUNT,
+            supply in 1u64..MAX_AMOUNT,
+            burned in 0u64..MAX_AMOUNT,
+        ) {
+            let burned = burned.min(supply);
+            let tokens = lst_to_token(burned, backing, supply).unwrap();
+            let minted = token_to_lst(tokens, backing - tokens, supply - burned).unwrap();
+            prop_assert!(minted <= burned);
+        }
+
+        #[test]
+        fn reward_index_never_overpays(
+            reward in 0u64..MAX_AMOUNT,
+            first in 1u64..MAX_AMOUNT,
+            second in 0u64..MAX_AMOUNT,
+        ) {
+            let index_delta = reward_index_delta(reward, first + second).unwrap();
+            let paid = accrued_rewards(first, index_delta).unwrap() + accrued_rewards(second, index_delta).unwrap();
+            prop_assert!(paid <= reward);
+        }
+    }
+
+    quickcheck! {
+        fn token_to_lst_is_monotonic(a: u64, b: u64, backing: u64, supply: u64) -> TestResult {
+            let (low, high) = if a <= b { (a, b) } else { (b, a) };
+            match (token_to_lst(low, backing, supply), token_to_lst(high, backing, supply)) {
+                (Ok(low_out), Ok(high_out)) => TestResult::from_bool(low_out <= high_out),
+                _ => TestResult::discard(),
+            }
+        }
+
+        fn lst_to_token_is_monotonic(a: u64, b: u64, backing: u64, supply: u64) -> TestResult {
+            let (low, high) = if a <= b { (a, b) } else { (b, a) };
+            match (lst_to_token(low, backing, supply), lst_to_token(high, backing, supply)) {
+                (Ok(low_out), Ok(high_out)) => TestResult::from_bool(low_out <= high_out),
+                _ => TestResult::discard(),
+            }
+        }
+
+        fn conversions_error_instead_of_panicking(amount: u64, backing: u64, supply: u64) -> bool {
+            let _ = token_to_lst(amount, backing, supply);
+            let _ = lst_to_token(amount, backing, supply);
+            true
+        }
+    }
+
+    #[test]
+    fn first_deposit_mints_one_to_one() {
+        assert_eq!(token_to_lst(1_000_000_000, 0, 0).unwrap(), 1_000_000_000);
+    }
+
+    #[test]
+    fn redeem_without_backing_pays_nothing() {
+        assert_eq!(lst_to_token(2, 0, 2).unwrap(), 0);
+        assert_eq!(token_to_lst(0, 0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn redeem_is_capped_at_backing() {
+        assert_eq!(lst_to_token(1_000, 10, 1_000).unwrap(), 10);
+    }
+
+    #[test]
+    fn exchange_rate_is_not_capped_below_precision() {
+        assert_eq!(exchange_rate(500_000_000, 500_000_000).unwrap(), EXCHANGE_RATE_PRECISION);
+        assert_eq!(exchange_rate(0, 0).unwrap(), EXCHANGE_RATE_PRECISION);
+        assert_eq!(exchange_rate(1_000_000, 0).unwrap(), 2 * EXCHANGE_RATE_PRECISION);
+        assert_eq!(exchange_rate(5_000_000_000, 2_000_000_000).unwrap(), 2_499_250_374);
+    }
+
+    #[test]
+    fn fee_amount_rounds_down() {
+        assert_eq!(fee_amount(199, 1).unwrap(), 1);
+        assert_eq!(fee_amount(1_000, 10).unwrap(), 100);
+        assert!(fee_amount(u64::MAX, 200).is_err());
+    }
+
+    #[test]
+    fn compound_min_out_applies_slippage_to_the_pool_quote() {
+        // 100 quote into 900/900 reserves quotes 90 tokens
+        assert_eq!(compound_min_out(100, 900, 900, 0).unwrap(), 90);
+        assert_eq!(compound_min_out(100, 900, 900, 100).unwrap(), 89);
+        assert_eq!(compound_min_out(100, 900, 900, BPS_DENOMINATOR).unwrap(), 0);
+        assert!(compound_min_out(100, 900, 900, BPS_DENOMINATOR + 1).is_err());
+    }
+
+    #[test]
+    fn compound_swap_amount_is_capped_by_the_quote_reserve() {
+        assert_eq!(compound_swap_amount(50, 10_000).unwrap(), 50);
+        assert_eq!(compound_swap_amount(500, 10_000).unwrap(), 100);
+        assert_eq!(compound_swap_amount(500, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn compound_min_out_rejects_empty_reserves() {
+        assert!(compound_min_out(100, 0, 900, 50).is_err());
+        assert!(compound_min_out(100, 900, 0, 50).is_err());
+    }
+
+    #[test]
+    fn overflow_returns_error() {
+        assert!(lst_to_token(u64::MAX, u64::MAX, 0).is_err());
+        assert!(total_backing(u64::MAX, 1, 0).is_err());
+        assert!(total_backing(0, 0, 1).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::error::ErrorCode;
+use crate::states::pool::LiquidStakingPool;
+use crate::utils::math;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use stake_for_fee_interface::{
+    accounts::{FeeVault, FeeVaultAccount},
+    id as stake_for_fee_id,
+};
+
+pub const AMM_PROGRAM_ID: Pubkey = solana_program::pubkey!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
+pub const VAULT_PROGRAM_ID: Pubkey = solana_program::pubkey!("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
+
+/// Offset of `lp_mint` in a dynamic AMM `Pool` account, right after the discriminator
+pub const AMM_POOL_LP_MINT_OFFSET: usize = 8;
+/// Offsets of the vault accounts in a dynamic AMM `Pool` account
+pub const AMM_POOL_A_VAULT_OFFSET: usize = 104;
+pub const AMM_POOL_B_VAULT_OFFSET: usize = 136;
+pub const AMM_POOL_A_VAULT_LP_OFFSET: usize = 168;
+pub const AMM_POOL_B_VAULT_LP_OFFSET: usize = 200;
+/// Offsets of `total_amount`, `token_vault`, `token_mint` and `lp_mint` in a dynamic vault `Vault` account
+pub const VAULT_TOTAL_AMOUNT_OFFSET: usize = 11;
+pub const VAULT_TOKEN_VAULT_OFFSET: usize = 19;
+pub const VAULT_TOKEN_MINT_OFFSET: usize = 83;
+pub const VAULT_LP_MINT_OFFSET: usize = 115;
+/// Offsets of `amount` in an SPL token account and `supply` in an SPL mint
+pub const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+pub const MINT_SUPPLY_OFFSET: usize = 36;
+
+/// Anchor discriminator of the dynamic AMM `swap` instruction
+pub const AMM_SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+
+/// Seed of the anchor `event_cpi` authority PDA
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Reads a pubkey stored at `offset` of an account owned by `owner`
+pub fn read_pubkey(account: &AccountInfo, owner: &Pubkey, offset: usize) -> Result<Pubkey> {
+    if account.owner != owner {
+        return err!(ErrorCode::InvalidMeteoraAccount);
+    }
+    let data = account.try_borrow_data()?;
+    let bytes = data
+        .get(offset..offset + 32)
+        .ok_or(ErrorCode::AccountDeserializeFailed)?;
+    Pubkey::try_from(bytes).map_err(|_| error!(ErrorCode::AccountDeserializeFailed))
+}
+
+/// Reads a little-endian u64 stored at `offset` of an account owned by `owner`
+pub fn read_u64(account: &AccountInfo, owner: &Pubkey, offset: usize) -> Result<u64> {
+    if account.owner != owner {
+        return err!(ErrorCode::InvalidMeteoraAccount);
+    }
+    let data = account.try_borrow_data()?;
+    let bytes = data
+        .get(offset..offset + 8)
+        .ok_or(ErrorCode::AccountDeserializeFailed)?;
+    let bytes: [u8; 8] = bytes.try_into().map_err(|_| error!(ErrorCode::AccountDeserializeFailed))?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Mint and reserve of one side of a dynamic AMM pool: the pool's share of the vault's
+/// `total_amount`, by its vault LP balance
+pub fn amm_pool_reserve(
+    amm_pool: &AccountInfo,
+    vault: &AccountInfo,
+    vault_lp: &AccountInfo,
+    vault_lp_mint: &AccountInfo,
+    vault_offset: usize,
+    vault_lp_offset: usize,
+) -> Result<(Pubkey, u64)> {
+    if read_pubkey(amm_pool, &AMM_PROGRAM_ID, vault_offset)? != vault.key()
+        || read_pubkey(amm_pool, &AMM_PROGRAM_ID, vault_lp_offset)? != vault_lp.key()
+        || read_pubkey(vault, &VAULT_PROGRAM_ID, VAULT_LP_MINT_OFFSET)? != vault_lp_mint.key()
+    {
+        return err!(ErrorCode::InvalidMeteoraAccount);
+    }
+    let token_mint = read_pubkey(vault, &VAULT_PROGRAM_ID, VAULT_TOKEN_MINT_OFFSET)?;
+    let total_amount = read_u64(vault, &VAULT_PROGRAM_ID, VAULT_TOTAL_AMOUNT_OFFSET)?;
+    let lp_amount = read_u64(vault_lp, &anchor_spl::token::ID, TOKEN_ACCOUNT_AMOUNT_OFFSET)?;
+    let lp_supply = read_u64(vault_lp_mint, &anchor_spl::token::ID, MINT_SUPPLY_OFFSET)?;
+    if lp_supply == 0 {
+        return Ok((token_mint, 0));
+    }
+    Ok((token_mint, math::pro_rata(lp_amount, total_amount, lp_supply)?))
+}
+
+/// Derives the `event_cpi` authority of `program_id`
+pub fn event_authority(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], program_id).0
+}
+
+/// Stores the Meteora accounts of the pool's fee vault on the pool, so every CPI context
+/// can check the accounts it is given against them
+pub fn record_fee_vault_accounts(pool: &mut LiquidStakingPool, vault: &AccountInfo, fee_pool: &AccountInfo) -> Result<FeeVault> {
+    if *vault.owner != stake_for_fee_id() {
+        return err!(ErrorCode::InvalidCpiProgram);
+    }
+    let fee_vault = {
+        let data_ref = vault.try_borrow_data()?;
+        FeeVaultAccount::deserialize(&data_ref)?.0
+    };
+    if fee_vault.pool != fee_pool.key() {
+        return err!(ErrorCode::InvalidMeteoraAccount);
+    }
+    let lp_mint = read_pubkey(fee_pool, &AMM_PROGRAM_ID, AMM_POOL_LP_MINT_OFFSET)?;
+
+    pool.fee_pool = fee_vault.pool;
+    pool.stake_token_vault = fee_vault.stake_token_vault;
+    pool.quote_token_vault = fee_vault.quote_token_vault;
+    pool.top_staker_list = fee_vault.top_staker_list;
+    pool.full_balance_list = fee_vault.full_balance_list;
+    pool.lp_mint = lp_mint;
+    pool.lock_escrow = fee_vault.lock_escrow;
+    pool.escrow_vault = get_associated_token_address(&fee_vault.lock_escrow, &lp_mint);
+    Ok(fee_vault)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(owner: Pubkey, len: usize) -> Self {
+            Self { key: Pubkey::new_unique(), owner, lamports: 0, data: vec![0; len] }
+        }
+
+        fn write(&mut self, offset: usize, bytes: &[u8]) {
+            self.data[offset..offset + bytes.len()].copy_from_slice(bytes);
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(&self.key, false, false, &mut self.lamports, &mut self.data, &self.owner, false, 0)
+        }
+    }
+
+    fn reserve_accounts(total_amount: u64, lp_amount: u64, lp_supply: u64) -> [TestAccount; 4] {
+        let mut amm_pool = TestAccount::new(AMM_PROGRAM_ID, 240);
+        let mut vault = TestAccount::new(VAULT_PROGRAM_ID, 160);
+        let mut vault_lp = TestAccount::new(anchor_spl::token::ID, 165);
+        let mut vault_lp_mint = TestAccount::new(anchor_spl::token::ID, 82);
+        amm_pool.write(AMM_POOL_A_VAULT_OFFSET, vault.key.as_ref());
+        amm_pool.write(AMM_POOL_A_VAULT_LP_OFFSET, vault_lp.key.as_ref());
+        vault.write(VAULT_LP_MINT_OFFSET, vault_lp_mint.key.as_ref());
+        vault.write(VAULT_TOTAL_AMOUNT_OFFSET, &total_amount.to_le_bytes());
+        vault_lp.write(TOKEN_ACCOUNT_AMOUNT_OFFSET, &lp_amount.to_le_bytes());
+        vault_lp_mint.write(MINT_SUPPLY_OFFSET, &lp_supply.to_le_bytes());
+        [amm_pool, vault, vault_lp, vault_lp_mint]
+    }
+
+    #[test]
+    fn amm_pool_reserve_is_the_pool_share_of_the_vault() {
+        let [mut amm_pool, mut vault, mut vault_lp, mut vault_lp_mint] = reserve_accounts(1_000, 250, 500);
+        let (_, reserve) = amm_pool_reserve(
+            &amm_pool.info(),
+            &vault.info(),
+            &vault_lp.info(),
+            &vault_lp_mint.info(),
+            AMM_POOL_A_VAULT_OFFSET,
+            AMM_POOL_A_VAULT_LP_OFFSET,
+        )
+        .unwrap();
+        assert_eq!(reserve, 500);
+    }
+
+    #[test]
+    fn amm_pool_reserve_rejects_accounts_of_another_pool() {
+        let [mut amm_pool, mut vault, mut vault_lp, mut vault_lp_mint] = reserve_accounts(1_000, 250, 500);
+        assert!(amm_pool_reserve(
+            &amm_pool.info(),
+            &vault.info(),
+            &vault_lp.info(),
+            &vault_lp_mint.info(),
+            AMM_POOL_B_VAULT_OFFSET,
+            AMM_POOL_B_VAULT_LP_OFFSET,
+        )
+        .is_err());
+    }
+}
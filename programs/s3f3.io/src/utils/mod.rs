@@ -1,2 +1,11 @@
 pub mod token;
-pub use token::*;
\ No newline at end of file
+pub use token::*;
+
+pub mod math;
+pub use math::*;
+
+pub mod meteora;
+pub use meteora::*;
+
+pub mod lookup_table;
+pub use lookup_table::*;
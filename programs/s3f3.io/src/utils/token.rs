@@ -1,6 +1,8 @@
 use crate::error::ErrorCode;
+use crate::states::unstake_request::LstUnstakeRequest;
 use anchor_lang::prelude::*;
 use anchor_spl::{
+    metadata,
     token::{Token, TokenAccount},
     token_2022::{
         self,
@@ -116,6 +118,50 @@ pub fn token_burn<'a>(
     )
 }
 
+/// Burns a 1-of-1 unstake ticket held by `owner`, closing its token, metadata and edition
+/// accounts back to the owner
+pub fn burn_unstake_ticket<'a>(
+    owner: AccountInfo<'a>,
+    mint: AccountInfo<'a>,
+    token: AccountInfo<'a>,
+    metadata: AccountInfo<'a>,
+    edition: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    metadata_program: AccountInfo<'a>,
+) -> Result<()> {
+    metadata::burn_nft(
+        CpiContext::new(
+            metadata_program,
+            metadata::BurnNft {
+                metadata,
+                owner,
+                mint,
+                token,
+                edition,
+                spl_token: token_program,
+            },
+        ),
+        None,
+    )
+}
+
+/// Authorizes `holder` to withdraw or cancel `request`, burning its ticket when it has one.
+/// Requests made before tickets can only be settled by the original staker.
+pub fn settle_unstake_ticket<'a>(
+    request: &LstUnstakeRequest,
+    holder: AccountInfo<'a>,
+    ticket_accounts: Option<[AccountInfo<'a>; 6]>,
+) -> Result<()> {
+    if !request.has_ticket() {
+        if request.owner != holder.key() {
+            return err!(ErrorCode::InvalidUnstakeRequestOwner);
+        }
+        return Ok(());
+    }
+    let [mint, token, metadata, edition, token_program, metadata_program] = ticket_accounts.ok_or(ErrorCode::InvalidUnstakeTicket)?;
+    burn_unstake_ticket(holder, mint, token, metadata, edition, token_program, metadata_program)
+}
+
 /// Calculate the fee for output amount
 pub fn get_transfer_inverse_fee(mint_info: &AccountInfo, post_fee_amount: u64) -> Result<u64> {
     if *mint_info.owner == Token::id() {
@@ -136,7 +182,7 @@ pub fn get_transfer_inverse_fee(mint_info: &AccountInfo, post_fee_amount: u64) -
         } else {
             transfer_fee_config
                 .calculate_inverse_epoch_fee(epoch, post_fee_amount)
-                .unwrap()
+                .ok_or(ErrorCode::OverflowError)?
         }
     } else {
         0
@@ -155,7 +201,7 @@ pub fn get_transfer_fee(mint_info: &AccountInfo, pre_fee_amount: u64) -> Result<
     let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
         transfer_fee_config
             .calculate_epoch_fee(Clock::get()?.epoch, pre_fee_amount)
-            .unwrap()
+            .ok_or(ErrorCode::OverflowError)?
     } else {
         0
     };
@@ -37,6 +37,7 @@ import {
   DYNAMIC_AMM_PROGRAM_ID,
   DYNAMIC_VAULT_PROGRAM_ID,
   EVENT_AUTHORITY,
+  TOKEN_METADATA_PROGRAM_ID,
   TOKEN_MINT_KEY,
   USER_KEY,
   VAULT_KEY,
@@ -44,8 +45,19 @@ import {
 import { StakeForFee } from "./stake";
 import { createStakeFeeProgram } from "./stake/helpers";
 import { StakeForFee as StakeForFeeProgram } from "./stake/idls/stake_for_fee";
-import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
-import { getAddressLookupTable, getLiquidStakingPoolAddress } from "./lib/pda";
+import {
+  AccountLayout,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  createTransferInstruction,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import {
+  getAddressLookupTable,
+  getLiquidStakingPoolAddress,
+  getNextLstUnstakeRequestAddress,
+  getUnstakeTicketMintAddress,
+} from "./lib/pda";
 import { Vememe } from "../target/types/vememe";
 
 dotenv.config();
@@ -137,6 +149,10 @@ describe("test", () => {
             "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB"
           ),
         },
+        {
+          name: "metadataprog",
+          programId: TOKEN_METADATA_PROGRAM_ID,
+        },
       ],
       await copy_data([smallestStaker])
     );
@@ -314,7 +330,15 @@ describe("test", () => {
     // console.log(escrowAccountData.stakeAmount.toNumber());
 
     const unstakeKeypair = Keypair.generate();
-    const requestUnstakeKeypair = Keypair.generate();
+    const [liquidStakingPool] = await getLiquidStakingPoolAddress(
+      program.programId,
+      TOKEN_MINT_KEY
+    );
+    const [lstUnstakeRequest] = await getNextLstUnstakeRequestAddress(
+      program,
+      liquidStakingPool,
+      owner.publicKey
+    );
 
     const ixUnstake = await requestUnstake(
       1000000000,
@@ -322,7 +346,6 @@ describe("test", () => {
       owner.payer,
       stakeForFee,
       unstakeKeypair,
-      requestUnstakeKeypair,
       TOKEN_MINT_KEY,
       VAULT_KEY
     );
@@ -337,11 +360,7 @@ describe("test", () => {
     }).compileToV0Message([]);
 
     const transactionV0Unstake = new VersionedTransaction(txUnstake);
-    transactionV0Unstake.sign([
-      owner.payer,
-      unstakeKeypair,
-      requestUnstakeKeypair,
-    ]);
+    transactionV0Unstake.sign([owner.payer, unstakeKeypair]);
     const txHashUnstake = await client.processTransaction(transactionV0Unstake);
 
     const currentClock = await client.getClock();
@@ -363,7 +382,7 @@ describe("test", () => {
       stakeForFee,
       TOKEN_MINT_KEY,
       unstakeKeypair.publicKey,
-      requestUnstakeKeypair.publicKey,
+      lstUnstakeRequest,
       VAULT_KEY
     );
 
@@ -408,6 +427,148 @@ describe("test", () => {
     //     console.log("Your transaction signature", unstakeTxHash);
   });
 
+  it("Withdraws as the holder of a transferred unstake ticket", async () => {
+    const ixsStake = await stake(
+      1000000000,
+      program,
+      owner.payer,
+      stakeForFee,
+      TOKEN_MINT_KEY,
+      VAULT_KEY
+    );
+    const txStake = new TransactionMessage({
+      payerKey: owner.publicKey,
+      recentBlockhash: context.lastBlockhash,
+      instructions: [
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 600000 }),
+        ...ixsStake,
+      ],
+    }).compileToV0Message([]);
+    const transactionV0Stake = new VersionedTransaction(txStake);
+    transactionV0Stake.sign([owner.payer]);
+    await client.processTransaction(transactionV0Stake);
+
+    const unstakeKeypair = Keypair.generate();
+    const [liquidStakingPool] = await getLiquidStakingPoolAddress(
+      program.programId,
+      TOKEN_MINT_KEY
+    );
+    const [lstUnstakeRequest] = await getNextLstUnstakeRequestAddress(
+      program,
+      liquidStakingPool,
+      owner.publicKey
+    );
+    const ixUnstake = await requestUnstake(
+      1000000000,
+      program,
+      owner.payer,
+      stakeForFee,
+      unstakeKeypair,
+      TOKEN_MINT_KEY,
+      VAULT_KEY
+    );
+    const txUnstake = new TransactionMessage({
+      payerKey: owner.publicKey,
+      recentBlockhash: context.lastBlockhash,
+      instructions: [
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 600000 }),
+        ...ixUnstake,
+      ],
+    }).compileToV0Message([]);
+    const transactionV0Unstake = new VersionedTransaction(txUnstake);
+    transactionV0Unstake.sign([owner.payer, unstakeKeypair]);
+    await client.processTransaction(transactionV0Unstake);
+
+    // Hand the ticket to a new holder, who then withdraws in place of the original staker
+    const holder = Keypair.generate();
+    context.setAccount(holder.publicKey, {
+      lamports: LAMPORTS_PER_SOL,
+      data: Buffer.alloc(0),
+      owner: SystemProgram.programId,
+      executable: false,
+    });
+    const [ticketMint] = await getUnstakeTicketMintAddress(
+      program.programId,
+      lstUnstakeRequest
+    );
+    const ownerTicketAccount = getAssociatedTokenAddressSync(
+      ticketMint,
+      owner.publicKey
+    );
+    const holderTicketAccount = getAssociatedTokenAddressSync(
+      ticketMint,
+      holder.publicKey
+    );
+    const holderTokenVault = getAssociatedTokenAddressSync(
+      TOKEN_MINT_KEY,
+      holder.publicKey
+    );
+    const txTransfer = new Transaction();
+    txTransfer.recentBlockhash = context.lastBlockhash;
+    txTransfer.add(
+      createAssociatedTokenAccountInstruction(
+        owner.publicKey,
+        holderTicketAccount,
+        holder.publicKey,
+        ticketMint
+      ),
+      createAssociatedTokenAccountInstruction(
+        owner.publicKey,
+        holderTokenVault,
+        holder.publicKey,
+        TOKEN_MINT_KEY
+      ),
+      createTransferInstruction(
+        ownerTicketAccount,
+        holderTicketAccount,
+        owner.publicKey,
+        1
+      )
+    );
+    txTransfer.sign(owner.payer);
+    await client.processTransaction(txTransfer);
+
+    const currentClock = await client.getClock();
+    context.setClock(
+      new Clock(
+        currentClock.slot,
+        currentClock.epochStartTimestamp,
+        currentClock.epoch,
+        currentClock.leaderScheduleEpoch,
+        currentClock.unixTimestamp + BigInt(31241412412)
+      )
+    );
+
+    const ixWithdraw = await withdraw(
+      program,
+      holder,
+      stakeForFee,
+      TOKEN_MINT_KEY,
+      unstakeKeypair.publicKey,
+      lstUnstakeRequest,
+      VAULT_KEY,
+      holderTokenVault
+    );
+    const txWithdraw = new TransactionMessage({
+      payerKey: holder.publicKey,
+      recentBlockhash: context.lastBlockhash,
+      instructions: [
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 600000 }),
+        ...ixWithdraw,
+      ],
+    }).compileToV0Message([]);
+    const transactionV0Withdraw = new VersionedTransaction(txWithdraw);
+    transactionV0Withdraw.sign([holder]);
+    await client.processTransaction(transactionV0Withdraw);
+
+    expect(await client.getAccount(lstUnstakeRequest)).toBeNull();
+    expect(await client.getAccount(holderTicketAccount)).toBeNull();
+    const holderTokenAccount = await client.getAccount(holderTokenVault);
+    expect(
+      AccountLayout.decode(holderTokenAccount!.data).amount > BigInt(0)
+    ).toBe(true);
+  });
+
   //   afterAll(async function () {
   //     console.log(
   //       "Tests finished! Keeping process alive. Press Ctrl+C to exit..."
@@ -21,4 +21,8 @@ export const EVENT_AUTHORITY = new PublicKey(
   "5b4WFMuinigYEDxSmLJhZf5wBbxRhvFPDvNmhfaB2BbF"
 );
 
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
 
@@ -25,6 +25,7 @@ import {
   getOrCreateAssociatedTokenAccount,
   transfer,
   createAssociatedTokenAccountInstruction,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   getConfigAddress,
@@ -32,6 +33,11 @@ import {
   getLiquidStakingPoolAddress,
   getPoolLiquidMintAddress,
   getPoolVaultAddress,
+  getUnstakeRequestCounterAddress,
+  getNextLstUnstakeRequestAddress,
+  getUnstakeTicketMintAddress,
+  getTicketMetadataAddress,
+  getTicketEditionAddress,
 } from "./index";
 
 import {
@@ -39,6 +45,7 @@ import {
   DYNAMIC_VAULT_PROGRAM_ID,
   EVENT_AUTHORITY,
   STAKE_FOR_FEE_PROGRAM_ID,
+  TOKEN_METADATA_PROGRAM_ID,
 } from "./constants";
 import {
   deriveFullBalanceList,
@@ -208,7 +215,7 @@ export async function initialize_escrow(
       vault: vault,
       topStakerList: topStakerListKey,
       fullBalanceList: fullBalanceListKey,
-      eventAuthority: EVENT_AUTHORITY,
+      cpiEventAuthority: EVENT_AUTHORITY,
       systemProgram: SystemProgram.programId,
     })
     .instruction();
@@ -361,15 +368,50 @@ export async function stake(
   return instructions;
 }
 
+// Ticket accounts `withdraw` and `cancelUnstake` burn from `holder`, all null for a request migrated without a ticket
+async function getUnstakeTicketAccounts(
+  program: Program<Rememe>,
+  lstUnstakeRequest: PublicKey,
+  holder: PublicKey
+) {
+  const { ticketMint } = await program.account.lstUnstakeRequest.fetch(
+    lstUnstakeRequest
+  );
+  if (ticketMint.equals(PublicKey.default)) {
+    return {
+      ticketMint: null,
+      stakerTicketAccount: null,
+      ticketMetadata: null,
+      ticketEdition: null,
+      metadataProgram: null,
+      ticketTokenProgram: null,
+    };
+  }
+  const [ticketMetadata, _ticketMetadataBump] = await getTicketMetadataAddress(
+    ticketMint
+  );
+  const [ticketEdition, _ticketEditionBump] = await getTicketEditionAddress(
+    ticketMint
+  );
+  return {
+    ticketMint: ticketMint,
+    stakerTicketAccount: getAssociatedTokenAddressSync(ticketMint, holder),
+    ticketMetadata: ticketMetadata,
+    ticketEdition: ticketEdition,
+    metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+    ticketTokenProgram: TOKEN_PROGRAM_ID,
+  };
+}
+
 export async function requestUnstake(
   amount: number,
   program: Program<Rememe>,
   owner: Signer,
   stakeForFee: StakeForFee,
   unstake: Signer,
-  lstUnstakeRequest: Signer,
   tokenMint: PublicKey,
-  vault: PublicKey
+  vault: PublicKey,
+  minAmountOut: number = 0
 ): Promise<TransactionInstruction[]> {
   const [config, _configBump] = await getConfigAddress(program.programId);
   const [auth, _authBump] = await getAuthAddress(program.programId);
@@ -415,6 +457,32 @@ export async function requestUnstake(
   const lookupTable = (
     await program.account.liquidStakingPool.fetch(liquidStakingPool)
   ).lut;
+  const [unstakeRequestCounter, _counterBump] =
+    await getUnstakeRequestCounterAddress(
+      program.programId,
+      liquidStakingPool,
+      owner.publicKey
+    );
+  const [lstUnstakeRequest, _lstUnstakeRequestBump] =
+    await getNextLstUnstakeRequestAddress(
+      program,
+      liquidStakingPool,
+      owner.publicKey
+    );
+  const [ticketMint, _ticketMintBump] = await getUnstakeTicketMintAddress(
+    program.programId,
+    lstUnstakeRequest
+  );
+  const [ticketMetadata, _ticketMetadataBump] = await getTicketMetadataAddress(
+    ticketMint
+  );
+  const [ticketEdition, _ticketEditionBump] = await getTicketEditionAddress(
+    ticketMint
+  );
+  const stakerTicketAccount = getAssociatedTokenAddressSync(
+    ticketMint,
+    owner.publicKey
+  );
 
   const instructions: TransactionInstruction[] = [];
 
@@ -456,9 +524,12 @@ export async function requestUnstake(
   }
 
   const unstakeIx = await program.methods
-    .requestUnstake(new BN(amount))
+    .requestUnstake(new BN(amount), new BN(minAmountOut))
     .accounts({
-      lstUnstakeRequest: lstUnstakeRequest.publicKey,
+      config: config,
+      stakerPosition: null,
+      unstakeRequestCounter: unstakeRequestCounter,
+      lstUnstakeRequest: lstUnstakeRequest,
       unstake: unstake.publicKey,
       staker: owner.publicKey,
       liquidStakingPool: liquidStakingPool,
@@ -478,6 +549,14 @@ export async function requestUnstake(
       lpMint: stakeForFee.accountStates.ammPool.lpMint,
       lockEscrow: stakeForFee.accountStates.feeVault.lockEscrow,
       escrowVault: escrowVaultKey,
+      ticketMint: ticketMint,
+      stakerTicketAccount: stakerTicketAccount,
+      ticketMetadata: ticketMetadata,
+      ticketEdition: ticketEdition,
+      metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      ticketTokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      rent: SYSVAR_RENT_PUBKEY,
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
     })
@@ -485,7 +564,7 @@ export async function requestUnstake(
       ...createRemainingAccounts(stakeForFee),
       ...remainingAccounts,
     ])
-    .signers([unstake, lstUnstakeRequest])
+    .signers([unstake])
     .instruction();
 
   instructions.push(unstakeIx);
@@ -499,7 +578,8 @@ export async function cancelUnstake(
   unstake: PublicKey,
   lstUnstakeRequest: PublicKey,
   tokenMint: PublicKey,
-  vault: PublicKey
+  vault: PublicKey,
+  minLiquidOut: number = 0
 ): Promise<TransactionInstruction[]> {
   const [config, _configBump] = await getConfigAddress(program.programId);
   const [auth, _authBump] = await getAuthAddress(program.programId);
@@ -569,9 +649,16 @@ export async function cancelUnstake(
     remainingAccounts.push(...smallestStakeEscrows);
   }
   console.log("FWAAFWWAF", remainingAccounts)
+  const ticketAccounts = await getUnstakeTicketAccounts(
+    program,
+    lstUnstakeRequest,
+    owner.publicKey
+  );
   const unstakeIx = await program.methods
-    .cancelUnstake()
+    .cancelUnstake(new BN(minLiquidOut))
     .accounts({
+      config: config,
+      stakerPosition: null,
       lstUnstakeRequest: lstUnstakeRequest,
       unstake: unstake,
       staker: owner.publicKey,
@@ -595,6 +682,7 @@ export async function cancelUnstake(
       lpMint: stakeForFee.accountStates.ammPool.lpMint,
       lockEscrow: stakeForFee.accountStates.feeVault.lockEscrow,
       escrowVault: escrowVaultKey,
+      ...ticketAccounts,
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
     })
@@ -614,8 +702,12 @@ export async function withdraw(
   tokenMint: PublicKey,
   unstake: PublicKey,
   lstUnstakeRequest: PublicKey,
-  vault: PublicKey
+  vault: PublicKey,
+  stakerTokenVault: PublicKey = new PublicKey(
+    "Hsg2hR4gUQ2NHQn6PPcqiDWPGwZiChrqCPDeLTz3UVSN"
+  )
 ): Promise<TransactionInstruction[]> {
+  const [config, _configBump] = await getConfigAddress(program.programId);
   const [auth, _authBump] = await getAuthAddress(program.programId);
   const [liquidStakingPool, _liquidStakingPoolBump] =
     await getLiquidStakingPoolAddress(program.programId, tokenMint);
@@ -646,26 +738,33 @@ export async function withdraw(
     TOKEN_PROGRAM_ID
   );
 
+  const ticketAccounts = await getUnstakeTicketAccounts(
+    program,
+    lstUnstakeRequest,
+    owner.publicKey
+  );
+
   const instructions: TransactionInstruction[] = [];
 
   const stakeIx = await program.methods
     .withdraw()
     .accounts({
+      config: config,
+      stakerPosition: null,
       staker: owner.publicKey,
       liquidStakingPool: liquidStakingPool,
       authority: auth,
       tokenVault: tokenVault,
-      stakerTokenVault: new PublicKey(
-        "Hsg2hR4gUQ2NHQn6PPcqiDWPGwZiChrqCPDeLTz3UVSN"
-      ),
+      stakerTokenVault: stakerTokenVault,
       lstUnstakeRequest: lstUnstakeRequest,
       unstake: unstake,
       cpiProgram: STAKE_FOR_FEE_PROGRAM_ID,
       vault: vault,
       stakeTokenVault: stakeForFee.accountStates.feeVault.stakeTokenVault,
       stakeEscrow: stakeEscrowKey,
+      ...ticketAccounts,
       tokenProgram: TOKEN_PROGRAM_ID,
-      eventAuthority: EVENT_AUTHORITY,
+      cpiEventAuthority: EVENT_AUTHORITY,
       systemProgram: SystemProgram.programId,
     })
     .instruction();
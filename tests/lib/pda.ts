@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { BN, Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { Rememe } from "../../target/types/rememe";
+import { TOKEN_METADATA_PROGRAM_ID } from "./constants";
 
 export const CONFIG_SEED = Buffer.from(
   anchor.utils.bytes.utf8.encode("config")
@@ -23,6 +24,26 @@ export const LIQUID_STAKING_POOL_VAULT_SEED = Buffer.from(
   anchor.utils.bytes.utf8.encode("liquid_staking_pool_vault")
 );  
 
+export const LST_UNSTAKE_REQUEST_SEED = Buffer.from(
+  anchor.utils.bytes.utf8.encode("lst_unstake_request")
+);
+
+export const UNSTAKE_REQUEST_COUNTER_SEED = Buffer.from(
+  anchor.utils.bytes.utf8.encode("unstake_request_counter")
+);
+
+export const UNSTAKE_TICKET_MINT_SEED = Buffer.from(
+  anchor.utils.bytes.utf8.encode("unstake_ticket_mint")
+);
+
+export const METADATA_SEED = Buffer.from(
+  anchor.utils.bytes.utf8.encode("metadata")
+);
+
+export const EDITION_SEED = Buffer.from(
+  anchor.utils.bytes.utf8.encode("edition")
+);
+
 export const getAddressLookupTable = async (program: Program<Rememe>, mintTo: PublicKey) => {
   const [address, bump] = await PublicKey.findProgramAddress(
     [LIQUID_STAKING_POOL_SEED, mintTo.toBuffer()],
@@ -86,4 +107,88 @@ export async function getPoolVaultAddress(
     programId
   );
   return [address, bump];
-}
\ No newline at end of file
+}
+
+export async function getUnstakeRequestCounterAddress(
+  programId: PublicKey,
+  pool: PublicKey,
+  owner: PublicKey
+): Promise<[PublicKey, number]> {
+  const [address, bump] = await PublicKey.findProgramAddress(
+    [UNSTAKE_REQUEST_COUNTER_SEED, pool.toBuffer(), owner.toBuffer()],
+    programId
+  );
+  return [address, bump];
+}
+
+export async function getLstUnstakeRequestAddress(
+  programId: PublicKey,
+  pool: PublicKey,
+  owner: PublicKey,
+  nonce: BN
+): Promise<[PublicKey, number]> {
+  const [address, bump] = await PublicKey.findProgramAddress(
+    [
+      LST_UNSTAKE_REQUEST_SEED,
+      pool.toBuffer(),
+      owner.toBuffer(),
+      nonce.toArrayLike(Buffer, "le", 8),
+    ],
+    programId
+  );
+  return [address, bump];
+}
+
+// The request the owner's next `requestUnstake` creates, at the counter's next nonce
+export async function getNextLstUnstakeRequestAddress(
+  program: Program<Rememe>,
+  pool: PublicKey,
+  owner: PublicKey
+): Promise<[PublicKey, number]> {
+  const [counter] = await getUnstakeRequestCounterAddress(
+    program.programId,
+    pool,
+    owner
+  );
+  const counterData = await program.account.unstakeRequestCounter.fetchNullable(
+    counter
+  );
+  const nonce = counterData ? counterData.nextNonce : new BN(0);
+  return getLstUnstakeRequestAddress(program.programId, pool, owner, nonce);
+}
+
+export async function getUnstakeTicketMintAddress(
+  programId: PublicKey,
+  lstUnstakeRequest: PublicKey
+): Promise<[PublicKey, number]> {
+  const [address, bump] = await PublicKey.findProgramAddress(
+    [UNSTAKE_TICKET_MINT_SEED, lstUnstakeRequest.toBuffer()],
+    programId
+  );
+  return [address, bump];
+}
+
+export async function getTicketMetadataAddress(
+  ticketMint: PublicKey
+): Promise<[PublicKey, number]> {
+  const [address, bump] = await PublicKey.findProgramAddress(
+    [METADATA_SEED, TOKEN_METADATA_PROGRAM_ID.toBuffer(), ticketMint.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  return [address, bump];
+}
+
+export async function getTicketEditionAddress(
+  ticketMint: PublicKey
+): Promise<[PublicKey, number]> {
+  const [address, bump] = await PublicKey.findProgramAddress(
+    [
+      METADATA_SEED,
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      ticketMint.toBuffer(),
+      EDITION_SEED,
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  return [address, bump];
+}
@@ -39,7 +39,11 @@ import {
   VAULT_KEY,
 } from "./lib/constants";
 import { TOKEN_PROGRAM_ID, transfer } from "@solana/spl-token";
-import { getAddressLookupTable, getLiquidStakingPoolAddress } from "./lib/pda";
+import {
+  getAddressLookupTable,
+  getLiquidStakingPoolAddress,
+  getNextLstUnstakeRequestAddress,
+} from "./lib/pda";
 import { IDL } from "../target/types/rememe";
 import { StakeForFee as StakeForFeeProgram } from "./stake/idls/stake_for_fee";
 
@@ -284,14 +288,17 @@ describe("rememe anchor test", () => {
       liquidPool
     );
     const unstakeKeypair = Keypair.generate();
-    const requestUnstakeKeypair = Keypair.generate();
+    const [lstUnstakeRequest] = await getNextLstUnstakeRequestAddress(
+      program,
+      liquidPool,
+      payer.publicKey
+    );
     const ixUnstake = await requestUnstake(
       900000000,
       program,
       payer,
       stakeForFee,
       unstakeKeypair,
-      requestUnstakeKeypair,
       TOKEN_MINT_KEY,
       VAULT_KEY
     );
@@ -304,7 +311,7 @@ describe("rememe anchor test", () => {
       ],
     }).compileToV0Message([lookupTableAccount]);
     const versionedTxUnstake = new VersionedTransaction(txUnstakeMsg);
-    versionedTxUnstake.sign([payer, unstakeKeypair, requestUnstakeKeypair]);
+    versionedTxUnstake.sign([payer, unstakeKeypair]);
     const txSigUnstake = await connection.sendTransaction(versionedTxUnstake, {
       skipPreflight: true
     });
@@ -315,7 +322,7 @@ describe("rememe anchor test", () => {
       payer,
       stakeForFee,
       unstakeKeypair.publicKey,
-      requestUnstakeKeypair.publicKey,
+      lstUnstakeRequest,
       TOKEN_MINT_KEY,
       VAULT_KEY
     );